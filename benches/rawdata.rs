@@ -0,0 +1,109 @@
+use std::hint::black_box;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use wp_model_core::raw::RawData;
+use wp_parse_api::processors::Strip;
+use wp_parse_api::{PipeHold, PipeProcessor, Pipeline, RawDataBuilder};
+
+const PAYLOAD_LEN: usize = 4096;
+
+fn variants() -> Vec<(&'static str, RawData)> {
+    let payload = vec![b'x'; PAYLOAD_LEN];
+    vec![
+        (
+            "string",
+            RawData::from_string(String::from_utf8(payload.clone()).unwrap()),
+        ),
+        ("bytes", RawData::Bytes(Bytes::from(payload.clone()))),
+        ("arc_bytes", RawData::from_arc_bytes(Arc::new(payload))),
+    ]
+}
+
+fn bench_as_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("as_bytes");
+    for (name, data) in variants() {
+        group.bench_function(name, |b| b.iter(|| black_box(data.as_bytes()).len()));
+    }
+    group.finish();
+}
+
+fn bench_into_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("into_bytes");
+    for (name, data) in variants() {
+        // Unique owner: `ArcBytes` moves its Vec without copying.
+        group.bench_function(format!("{name}/unique"), |b| {
+            b.iter_batched(
+                || match &data {
+                    RawData::ArcBytes(arc) => RawData::from_arc_bytes(Arc::new(arc.to_vec())),
+                    other => other.clone(),
+                },
+                |raw| black_box(raw.into_bytes()),
+                BatchSize::SmallInput,
+            )
+        });
+        // Shared owner: `ArcBytes` falls back to a copy.
+        group.bench_function(format!("{name}/shared"), |b| {
+            b.iter_batched(
+                || data.clone(),
+                |raw| black_box(raw.into_bytes()),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_slice(c: &mut Criterion) {
+    let strip = Strip::new(16, 16);
+    let mut group = c.benchmark_group("slice");
+    for (name, data) in variants() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || data.clone(),
+                |raw| black_box(strip.process(raw).unwrap()),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_concat(c: &mut Criterion) {
+    let parts: Vec<RawData> = variants().into_iter().map(|(_, data)| data).collect();
+    c.bench_function("concat/mixed", |b| {
+        b.iter_batched(
+            || parts.clone(),
+            |parts| black_box(parts.into_iter().collect::<RawDataBuilder>().build()),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    let pipeline: Pipeline = (0..4).fold(Pipeline::new(), |p, _| {
+        p.with(Arc::new(Strip::new(8, 8)) as PipeHold)
+    });
+    let mut group = c.benchmark_group("pipeline/strip_x4");
+    for (name, data) in variants() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || data.clone(),
+                |raw| black_box(pipeline.run(raw).unwrap()),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_as_bytes,
+    bench_into_bytes,
+    bench_slice,
+    bench_concat,
+    bench_pipeline
+);
+criterion_main!(benches);
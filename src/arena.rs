@@ -0,0 +1,95 @@
+//! Chunked allocation of many small [`RawData`] payloads.
+//!
+//! `RawData` has no lifetime parameter, so it cannot borrow from a bump
+//! arena. [`RawDataArena`] instead copies records into large shared chunks
+//! and hands out `RawData::Bytes` views of them: one heap allocation serves
+//! many records, and the views stay `'static` and `Send`.
+//!
+//! The tradeoff is retention. A chunk is freed only when every view into it
+//! has been dropped, so one long-lived record keeps its whole chunk alive.
+//! Use the arena for batches of short-lived records that are dropped
+//! together, not for a few records that outlive the rest.
+
+use bytes::{Bytes, BytesMut};
+use wp_model_core::raw::RawData;
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Copies small payloads into shared chunks to cut allocator traffic.
+///
+/// Records larger than a quarter of the chunk size get their own allocation,
+/// so large payloads never force a chunk to be abandoned half-empty.
+#[derive(Debug)]
+pub struct RawDataArena {
+    chunk: BytesMut,
+    chunk_size: usize,
+}
+
+impl Default for RawDataArena {
+    fn default() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+}
+
+impl RawDataArena {
+    /// Arena with 64 KiB chunks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        Self {
+            chunk: BytesMut::with_capacity(chunk_size),
+            chunk_size,
+        }
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Copy `bytes` into the arena and return a view of the copy.
+    pub fn alloc(&mut self, bytes: &[u8]) -> RawData {
+        if bytes.len() > self.chunk_size / 4 {
+            return RawData::Bytes(Bytes::copy_from_slice(bytes));
+        }
+        if self.chunk.capacity() - self.chunk.len() < bytes.len() {
+            self.chunk = BytesMut::with_capacity(self.chunk_size);
+        }
+        self.chunk.extend_from_slice(bytes);
+        RawData::Bytes(self.chunk.split().freeze())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_records_share_a_chunk() {
+        let mut arena = RawDataArena::with_chunk_size(64);
+        let a = arena.alloc(b"alpha");
+        let b = arena.alloc(b"beta");
+        assert_eq!(a.as_bytes(), b"alpha");
+        assert_eq!(b.as_bytes(), b"beta");
+        // Consecutive records sit back to back in the same allocation.
+        assert_eq!(
+            b.as_bytes().as_ptr() as usize - a.as_bytes().as_ptr() as usize,
+            5
+        );
+    }
+
+    #[test]
+    fn test_chunk_rollover_and_large_records() {
+        let mut arena = RawDataArena::with_chunk_size(16);
+        let records: Vec<RawData> = (0..10).map(|i| arena.alloc(&[i; 3])).collect();
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record.as_bytes(), [i as u8; 3]);
+        }
+
+        let big = arena.alloc(&[7; 100]);
+        assert_eq!(big.len(), 100);
+        assert_eq!(arena.alloc(b"x").as_bytes(), b"x");
+    }
+}
@@ -0,0 +1,95 @@
+//! Minimal RFC 4648 base64 codec (standard alphabet) shared by the helpers
+//! that need it, so the crate does not pull in a codec dependency.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const PAD: u8 = b'=';
+
+pub(crate) fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push(PAD as char);
+            }
+        }
+    }
+    out
+}
+
+fn sextet(c: u8) -> Option<u32> {
+    let v = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+    Some(u32::from(v))
+}
+
+/// Decode standard base64; trailing padding is optional.
+///
+/// Returns a human-readable reason on malformed input.
+pub(crate) fn decode(text: &[u8]) -> Result<Vec<u8>, String> {
+    let body = match text.iter().rposition(|&c| c != PAD) {
+        Some(last) => &text[..=last],
+        None => &text[..0],
+    };
+    let padding = text.len() - body.len();
+    if padding > 2 || (padding > 0 && !text.len().is_multiple_of(4)) {
+        return Err("invalid base64 padding".to_string());
+    }
+    if body.len() % 4 == 1 {
+        return Err("invalid base64 length".to_string());
+    }
+    let mut out = Vec::with_capacity(body.len() / 4 * 3 + 2);
+    for chunk in body.chunks(4) {
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let v = sextet(c).ok_or_else(|| format!("invalid base64 character 0x{:02x}", c))?;
+            n |= v << (18 - 6 * i);
+        }
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        out.extend_from_slice(&bytes[..chunk.len() - 1]);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+        for (plain, encoded) in vectors {
+            assert_eq!(encode(plain), encoded);
+            assert_eq!(decode(encoded.as_bytes()).unwrap(), plain);
+        }
+        assert_eq!(decode(b"Zm8").unwrap(), b"fo");
+    }
+
+    #[test]
+    fn test_decode_rejects_malformed() {
+        assert!(decode(b"Zm9v!A==").is_err());
+        assert!(decode(b"Z").is_err());
+        assert!(decode(b"Zg===").is_err());
+    }
+}
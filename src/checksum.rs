@@ -0,0 +1,141 @@
+//! Dependency-free checksums over [`RawData`](wp_model_core::raw::RawData)
+//! payloads.
+
+use std::str::FromStr;
+
+use crate::{WparseError, WparseReason};
+
+/// Checksum algorithm selectable by name, e.g. from processor configuration.
+///
+/// Non-exhaustive because the set of variants depends on enabled features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Checksum {
+    /// CRC-32 (IEEE 802.3, as used by gzip and zip).
+    Crc32,
+    /// Adler-32 (RFC 1950, as used by zlib).
+    Adler32,
+    /// SHA-256; requires the `crypto` feature.
+    #[cfg(feature = "crypto")]
+    Sha256,
+}
+
+impl Checksum {
+    pub fn name(self) -> &'static str {
+        match self {
+            Checksum::Crc32 => "crc32",
+            Checksum::Adler32 => "adler32",
+            #[cfg(feature = "crypto")]
+            Checksum::Sha256 => "sha256",
+        }
+    }
+
+    /// Digest of `bytes`; 32-bit checksums are returned big-endian.
+    pub fn digest(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Checksum::Crc32 => crc32(bytes).to_be_bytes().to_vec(),
+            Checksum::Adler32 => adler32(bytes).to_be_bytes().to_vec(),
+            #[cfg(feature = "crypto")]
+            Checksum::Sha256 => {
+                use sha2::Digest;
+                sha2::Sha256::digest(bytes).to_vec()
+            }
+        }
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = WparseError;
+
+    /// Parse a [`name`](Checksum::name); unknown or disabled algorithms are
+    /// reported as a core configuration error.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "crc32" => Ok(Checksum::Crc32),
+            "adler32" => Ok(Checksum::Adler32),
+            #[cfg(feature = "crypto")]
+            "sha256" => Ok(Checksum::Sha256),
+            _ => Err(WparseReason::core_conf()
+                .into_traced_err()
+                .with_detail(format!("unknown checksum: {}", name))),
+        }
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &b| {
+        CRC32_TABLE[((crc ^ u32::from(b)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+pub(crate) fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    // Largest block whose sums cannot overflow u32 before reduction.
+    const NMAX: usize = 5552;
+    let (mut a, mut b) = (1u32, 0u32);
+    for block in bytes.chunks(NMAX) {
+        for &byte in block {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+
+        let long = vec![0xFFu8; 100_000];
+        let naive = long.iter().fold((1u64, 0u64), |(a, b), &x| {
+            let a = (a + u64::from(x)) % 65521;
+            (a, (b + a) % 65521)
+        });
+        assert_eq!(adler32(&long), ((naive.1 << 16) | naive.0) as u32);
+    }
+
+    #[test]
+    fn test_checksum_by_name() {
+        let kind: Checksum = "adler32".parse().unwrap();
+        assert_eq!(kind.digest(b"Wikipedia"), [0x11, 0xE6, 0x03, 0x98]);
+        assert_eq!(kind.name(), "adler32");
+        assert!("md5".parse::<Checksum>().is_err());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_sha256_digest() {
+        let digest = Checksum::Sha256.digest(b"abc");
+        assert_eq!(digest.len(), 32);
+        assert_eq!(&digest[..4], [0xBA, 0x78, 0x16, 0xBF]);
+    }
+}
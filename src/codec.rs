@@ -0,0 +1,191 @@
+//! Length-delimited `tokio_util::codec` framing for [`RawData`].
+
+use std::io;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+use wp_model_core::raw::RawData;
+
+use crate::processors::ByteOrder;
+
+const DEFAULT_MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+/// Shared framing parameters of [`RawDataDecoder`] and [`RawDataEncoder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Framing {
+    header_size: usize,
+    byte_order: ByteOrder,
+    max_frame_len: usize,
+}
+
+impl Framing {
+    fn new(header_size: usize, byte_order: ByteOrder) -> Self {
+        assert!(
+            (1..=8).contains(&header_size),
+            "header size {} not in 1..=8",
+            header_size
+        );
+        Self {
+            header_size,
+            byte_order,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    fn too_long(&self, len: u64) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "frame of {} bytes exceeds limit {}",
+                len, self.max_frame_len
+            ),
+        )
+    }
+}
+
+/// Decodes frames of `header_size`-byte length prefix plus payload into
+/// `RawData::Bytes`, splitting them off the read buffer without copying.
+///
+/// Partial frames yield `Ok(None)` until enough bytes arrive. Frames longer
+/// than [`max_frame_len`](Self::max_frame_len) (8 MiB by default) fail with
+/// `io::ErrorKind::InvalidData`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawDataDecoder {
+    framing: Framing,
+}
+
+impl RawDataDecoder {
+    /// # Panics
+    /// If `header_size` is not in `1..=8`.
+    pub fn new(header_size: usize, byte_order: ByteOrder) -> Self {
+        Self {
+            framing: Framing::new(header_size, byte_order),
+        }
+    }
+
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.framing.max_frame_len = max_frame_len;
+        self
+    }
+}
+
+impl Decoder for RawDataDecoder {
+    type Item = RawData;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<RawData>> {
+        let Framing {
+            header_size,
+            byte_order,
+            max_frame_len,
+        } = self.framing;
+        if src.len() < header_size {
+            return Ok(None);
+        }
+        let mut header = &src[..header_size];
+        let len = match byte_order {
+            ByteOrder::Big => header.get_uint(header_size),
+            ByteOrder::Little => header.get_uint_le(header_size),
+        };
+        if len > max_frame_len as u64 {
+            return Err(self.framing.too_long(len));
+        }
+        let frame_end = header_size + len as usize;
+        if src.len() < frame_end {
+            src.reserve(frame_end - src.len());
+            return Ok(None);
+        }
+        src.advance(header_size);
+        Ok(Some(RawData::Bytes(src.split_to(len as usize).freeze())))
+    }
+}
+
+/// Encodes `RawData` as a `header_size`-byte length prefix plus payload.
+///
+/// Payloads longer than [`max_frame_len`](Self::max_frame_len) or than the
+/// header can express fail with `io::ErrorKind::InvalidData`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawDataEncoder {
+    framing: Framing,
+}
+
+impl RawDataEncoder {
+    /// # Panics
+    /// If `header_size` is not in `1..=8`.
+    pub fn new(header_size: usize, byte_order: ByteOrder) -> Self {
+        Self {
+            framing: Framing::new(header_size, byte_order),
+        }
+    }
+
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.framing.max_frame_len = max_frame_len;
+        self
+    }
+}
+
+impl Encoder<RawData> for RawDataEncoder {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: RawData, dst: &mut BytesMut) -> io::Result<()> {
+        let Framing {
+            header_size,
+            byte_order,
+            max_frame_len,
+        } = self.framing;
+        let len = item.len() as u64;
+        let bits = header_size * 8;
+        if len > max_frame_len as u64 || (bits < 64 && len >> bits != 0) {
+            return Err(self.framing.too_long(len));
+        }
+        dst.reserve(header_size + item.len());
+        match byte_order {
+            ByteOrder::Big => dst.put_uint(len, header_size),
+            ByteOrder::Little => dst.put_uint_le(len, header_size),
+        }
+        dst.extend_from_slice(item.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_partial_frames() {
+        let mut buf = BytesMut::new();
+        let mut encoder = RawDataEncoder::new(2, ByteOrder::Big);
+        encoder
+            .encode(RawData::from_string("abc"), &mut buf)
+            .unwrap();
+        encoder.encode(RawData::from_string(""), &mut buf).unwrap();
+        assert_eq!(&buf[..], b"\x00\x03abc\x00\x00");
+
+        let mut decoder = RawDataDecoder::new(2, ByteOrder::Big);
+        let mut src = BytesMut::new();
+        for &byte in &buf[..4] {
+            src.put_u8(byte);
+            assert!(decoder.decode(&mut src).unwrap().is_none());
+        }
+        src.extend_from_slice(&buf[4..]);
+        let frame = decoder.decode(&mut src).unwrap().unwrap();
+        assert!(matches!(&frame, RawData::Bytes(b) if &b[..] == b"abc"));
+        assert!(decoder.decode(&mut src).unwrap().unwrap().is_empty());
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_frame_limits() {
+        let mut decoder = RawDataDecoder::new(4, ByteOrder::Little).max_frame_len(4);
+        let mut src = BytesMut::from(&b"\x05\x00\x00\x00"[..]);
+        let err = decoder.decode(&mut src).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut encoder = RawDataEncoder::new(1, ByteOrder::Big);
+        let err = encoder
+            .encode(RawData::from_string("x".repeat(256)), &mut BytesMut::new())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
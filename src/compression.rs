@@ -0,0 +1,70 @@
+//! gzip (RFC 1952) and zlib (RFC 1950) codecs backing the `compression` feature.
+
+use std::io::{Read, Write};
+
+use flate2::Compression;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+
+use crate::{WparseError, WparseReason, WparseResult};
+
+fn codec_err(codec: &str, err: std::io::Error) -> WparseError {
+    WparseReason::Encoding(codec.to_string())
+        .into_traced_err()
+        .with_detail(err.to_string())
+}
+
+pub(crate) fn gzip_encode(data: &[u8]) -> WparseResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 2), Compression::default());
+    encoder
+        .write_all(data)
+        .and_then(|_| encoder.finish())
+        .map_err(|err| codec_err("gzip", err))
+}
+
+pub(crate) fn gzip_decode(data: &[u8]) -> WparseResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() * 2);
+    GzDecoder::new(data)
+        .read_to_end(&mut out)
+        .map_err(|err| codec_err("gzip", err))?;
+    Ok(out)
+}
+
+pub(crate) fn zlib_encode(data: &[u8]) -> WparseResult<Vec<u8>> {
+    zlib_encode_level(data, Compression::default().level())
+}
+
+pub(crate) fn zlib_decode(data: &[u8]) -> WparseResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() * 2);
+    ZlibDecoder::new(data)
+        .read_to_end(&mut out)
+        .map_err(|err| codec_err("zlib", err))?;
+    Ok(out)
+}
+
+/// [`zlib_decode`] that fails once the output would exceed `max_output`
+/// bytes, guarding against decompression bombs.
+pub(crate) fn zlib_decode_limited(data: &[u8], max_output: usize) -> WparseResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len().saturating_mul(2).min(max_output));
+    let limit = u64::try_from(max_output)
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    ZlibDecoder::new(data)
+        .take(limit)
+        .read_to_end(&mut out)
+        .map_err(|err| codec_err("zlib", err))?;
+    if out.len() > max_output {
+        return Err(WparseReason::Encoding("zlib".to_string())
+            .into_traced_err()
+            .with_detail(format!("decompressed output exceeds {} bytes", max_output)));
+    }
+    Ok(out)
+}
+
+pub(crate) fn zlib_encode_level(data: &[u8], level: u32) -> WparseResult<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len() / 2), Compression::new(level));
+    encoder
+        .write_all(data)
+        .and_then(|_| encoder.finish())
+        .map_err(|err| codec_err("zlib", err))
+}
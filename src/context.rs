@@ -0,0 +1,119 @@
+//! Ambient state shared by the steps of one pipeline run.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use wp_model_core::model::DataRecord;
+
+type Extensions = HashMap<TypeId, Box<dyn Any + Send + Sync>>;
+
+/// Context threaded through every step by [`Pipeline::run_with`].
+///
+/// Carries the offset of the payload within its original buffer, a
+/// [`DataRecord`] that steps may fill in as they go, and typed extensions
+/// keyed by their type (one value per type). Steps only get a shared
+/// reference, so the record and extensions are behind a lock; the context
+/// is `Send + Sync` and can be shared with steps that fan out.
+///
+/// [`Pipeline::run_with`]: crate::Pipeline::run_with
+#[derive(Default)]
+pub struct ParseContext {
+    offset: usize,
+    record: Mutex<DataRecord>,
+    extensions: Mutex<Extensions>,
+}
+
+impl ParseContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the offset of the payload within the original input.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Start from an existing record instead of an empty one.
+    pub fn with_record(self, record: DataRecord) -> Self {
+        Self {
+            record: Mutex::new(record),
+            ..self
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Lock the record being built. Do not hold the guard across a call
+    /// into another step.
+    pub fn record(&self) -> MutexGuard<'_, DataRecord> {
+        self.record.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The record built during the run.
+    pub fn into_record(self) -> DataRecord {
+        self.record.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Store `value` as the extension of type `T`, returning the previous one.
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> Option<T> {
+        self.extensions()
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast().ok().map(|old| *old))
+    }
+
+    /// Clone of the extension of type `T`, if set.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.extensions()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    pub fn remove<T: Send + Sync + 'static>(&self) -> Option<T> {
+        self.extensions()
+            .remove(&TypeId::of::<T>())
+            .and_then(|old| old.downcast().ok().map(|old| *old))
+    }
+
+    fn extensions(&self) -> MutexGuard<'_, Extensions> {
+        self.extensions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for ParseContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseContext")
+            .field("offset", &self.offset)
+            .field("record_fields", &self.record().len())
+            .field("extensions", &self.extensions().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Tenant(&'static str);
+
+    #[test]
+    fn test_typed_extensions() {
+        let ctx = ParseContext::new().with_offset(12);
+        assert_eq!(ctx.offset(), 12);
+        assert_eq!(ctx.get::<Tenant>(), None);
+
+        assert_eq!(ctx.insert(Tenant("a")), None);
+        assert_eq!(ctx.insert(7u32), None);
+        assert_eq!(ctx.insert(Tenant("b")), Some(Tenant("a")));
+        assert_eq!(ctx.get::<Tenant>(), Some(Tenant("b")));
+        assert_eq!(ctx.get::<u32>(), Some(7));
+        assert_eq!(ctx.remove::<u32>(), Some(7));
+        assert_eq!(ctx.get::<u32>(), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! Splitting of single CSV lines into fields.
+
+use std::ops::Range;
+
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::raw_ext::{shared_slice, with_shared};
+use crate::{WparseError, WparseResult};
+
+/// A field located in the line, or one rebuilt because it held escaped quotes.
+enum Field {
+    Span(Range<usize>),
+    Unescaped(Vec<u8>),
+}
+
+/// Split one CSV line into its fields, RFC 4180 style.
+///
+/// A field starting with `quote` is quoted: it may contain `delim` and line
+/// breaks, a doubled `quote` stands for one literal quote, and the closing
+/// quote must be followed by `delim` or the end of the line. Fields are
+/// zero-copy slices of the line, except quoted fields with escaped quotes,
+/// which are copied. Empty input yields one empty field; strip the line
+/// terminator first (e.g. with `RawDataExt::lines`).
+///
+/// An unterminated quoted field, text after a closing quote or a `quote`
+/// inside an unquoted field fails with `FormatError`.
+pub fn parse_csv_line(data: &RawData, delim: u8, quote: u8) -> WparseResult<Vec<RawData>> {
+    let bytes = data.as_bytes();
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    loop {
+        let (field, end) = if bytes.get(pos) == Some(&quote) {
+            quoted_field(bytes, pos, quote)?
+        } else {
+            let end = bytes[pos..]
+                .iter()
+                .position(|&b| b == delim)
+                .map_or(bytes.len(), |at| pos + at);
+            if let Some(at) = bytes[pos..end].iter().position(|&b| b == quote) {
+                return Err(csv_error("quote inside unquoted field", pos + at));
+            }
+            (Field::Span(pos..end), end)
+        };
+        fields.push(field);
+        match bytes.get(end) {
+            None => break,
+            Some(&b) if b == delim => pos = end + 1,
+            Some(_) => return Err(csv_error("text after closing quote", end)),
+        }
+    }
+    Ok(with_shared(data, |data| {
+        fields
+            .into_iter()
+            .map(|field| match field {
+                Field::Span(range) => shared_slice(data, range),
+                Field::Unescaped(bytes) => match data {
+                    RawData::String(_) => match String::from_utf8(bytes) {
+                        Ok(text) => RawData::String(text),
+                        Err(err) => RawData::Bytes(Bytes::from(err.into_bytes())),
+                    },
+                    _ => RawData::Bytes(Bytes::from(bytes)),
+                },
+            })
+            .collect()
+    }))
+}
+
+/// Parse the quoted field opening at `start`; returns it and the offset just
+/// past the closing quote.
+fn quoted_field(bytes: &[u8], start: usize, quote: u8) -> WparseResult<(Field, usize)> {
+    let mut unescaped: Option<Vec<u8>> = None;
+    let mut i = start + 1;
+    loop {
+        let Some(at) = bytes[i..].iter().position(|&b| b == quote) else {
+            return Err(csv_error("unterminated quoted field", start));
+        };
+        let close = i + at;
+        if bytes.get(close + 1) == Some(&quote) {
+            // Doubled quote: keep one and continue after both.
+            unescaped
+                .get_or_insert_with(Vec::new)
+                .extend_from_slice(&bytes[i..=close]);
+            i = close + 2;
+            continue;
+        }
+        let field = match unescaped {
+            Some(mut buf) => {
+                buf.extend_from_slice(&bytes[i..close]);
+                Field::Unescaped(buf)
+            }
+            None => Field::Span(start + 1..close),
+        };
+        return Ok((field, close + 1));
+    }
+}
+
+fn csv_error(what: &str, at: usize) -> WparseError {
+    DataErrKind::FormatError(format!("parse_csv_line: {} at byte {}", what, at), None).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn texts(fields: &[RawData]) -> Vec<&[u8]> {
+        fields.iter().map(|f| f.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_quoted_fields_and_escapes() {
+        let line = RawData::from_string(r#"1,"Doe, Jane","say ""hi""",,"""#);
+        let fields = parse_csv_line(&line, b',', b'"').unwrap();
+        assert_eq!(
+            texts(&fields),
+            [&b"1"[..], b"Doe, Jane", br#"say "hi""#, b"", b""]
+        );
+        assert!(matches!(&fields[2], RawData::String(_)));
+
+        assert_eq!(
+            texts(&parse_csv_line(&RawData::from_string(""), b',', b'"').unwrap()),
+            [b""]
+        );
+        let tsv = RawData::from_string("a\t'b\tc'");
+        assert_eq!(
+            texts(&parse_csv_line(&tsv, b'\t', b'\'').unwrap()),
+            [&b"a"[..], b"b\tc"]
+        );
+    }
+
+    #[test]
+    fn test_plain_fields_are_zero_copy() {
+        let arc = Arc::new(br#"abc,"def""#.to_vec());
+        let fields = parse_csv_line(&RawData::from_arc_bytes(arc.clone()), b',', b'"').unwrap();
+        assert_eq!(texts(&fields), [&b"abc"[..], b"def"]);
+        assert_eq!(fields[0].as_bytes().as_ptr(), arc.as_ptr());
+        assert_eq!(fields[1].as_bytes().as_ptr(), arc[5..].as_ptr());
+    }
+
+    #[test]
+    fn test_malformed_quoting() {
+        for bad in [r#"a,"open"#, r#""x"y,z"#, r#"a"b,c"#] {
+            let err = parse_csv_line(&RawData::from_string(bad), b',', b'"').unwrap_err();
+            assert_eq!(err.reason(), &crate::WparseReason::data_error());
+            assert!(
+                err.detail()
+                    .as_deref()
+                    .unwrap()
+                    .starts_with("format error : parse_csv_line"),
+                "{bad}"
+            );
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Byte-level diffs between two payloads, for debugging parsers and test
+//! failure messages.
+
+use std::fmt::Write;
+
+/// One run of a byte-level edit script from `self` to `other`.
+///
+/// Runs are ordered. Offsets index into `self`. Applying them in order
+/// rebuilds `other`: `Equal(n)` copies the next `n` bytes of `self`,
+/// `Delete` skips `count` bytes at `offset`, and `Insert` emits `bytes`
+/// before position `offset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawDataDiff {
+    Equal(usize),
+    Insert { offset: usize, bytes: Vec<u8> },
+    Delete { offset: usize, count: usize },
+}
+
+#[derive(Clone, Copy)]
+enum Edit {
+    Equal,
+    Insert(u8),
+    Delete,
+}
+
+/// Edit distance beyond which [`diff`] stops searching for a shortest
+/// script. Memory for the search grows with the square of the distance, so
+/// past this bound the differing middle is reported as one `Delete` and one
+/// `Insert` instead.
+const MAX_EDIT_DISTANCE: usize = 1024;
+
+/// Shortest edit script between `a` and `b` (Myers, O((N+M)·D) time) once
+/// their common prefix and suffix are set aside, or a single replacement of
+/// the middle if it needs more than [`MAX_EDIT_DISTANCE`] edits.
+pub(crate) fn diff(a: &[u8], b: &[u8]) -> Vec<RawDataDiff> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a_mid, b_mid) = (&a[prefix..], &b[prefix..]);
+    let suffix = a_mid
+        .iter()
+        .rev()
+        .zip(b_mid.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_mid = &a_mid[..a_mid.len() - suffix];
+    let b_mid = &b_mid[..b_mid.len() - suffix];
+
+    let mut edits = vec![Edit::Equal; prefix];
+    match myers(a_mid, b_mid, MAX_EDIT_DISTANCE) {
+        Some(middle) => edits.extend(middle),
+        None => {
+            edits.extend(std::iter::repeat_n(Edit::Delete, a_mid.len()));
+            edits.extend(b_mid.iter().map(|&byte| Edit::Insert(byte)));
+        }
+    }
+    edits.extend(std::iter::repeat_n(Edit::Equal, suffix));
+    coalesce(&edits)
+}
+
+/// Myers' search for an edit script of at most `limit` edits.
+///
+/// Round `d` only touches diagonals `-d..=d`, so the trace keeps just those
+/// `2d + 1` entries of `v` per round.
+fn myers(a: &[u8], b: &[u8], limit: usize) -> Option<Vec<Edit>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m).min(limit as isize);
+    let idx = |k: isize| (k + max + 1) as usize;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: {
+        for d in 0..=max {
+            trace.push(v[idx(-d)..=idx(d)].to_vec());
+            for k in (-d..=d).step_by(2) {
+                let mut x = if k == -d || (k != d && v[idx(k - 1)] < v[idx(k + 1)]) {
+                    v[idx(k + 1)]
+                } else {
+                    v[idx(k - 1)] + 1
+                };
+                let mut y = x - k;
+                while x < n && y < m && a[x as usize] == b[y as usize] {
+                    x += 1;
+                    y += 1;
+                }
+                v[idx(k)] = x;
+                if x >= n && y >= m {
+                    break 'search;
+                }
+            }
+        }
+        return None;
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, round) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        // `round[0]` holds diagonal `-d`.
+        let at = |k: isize| round[(k + d) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let (prev_x, prev_y) = if d > 0 {
+            let prev_x = at(prev_k);
+            (prev_x, prev_x - prev_k)
+        } else {
+            (0, 0)
+        };
+        while x > prev_x && y > prev_y {
+            edits.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert(b[prev_y as usize]));
+            } else {
+                edits.push(Edit::Delete);
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    Some(edits)
+}
+
+fn coalesce(edits: &[Edit]) -> Vec<RawDataDiff> {
+    let mut out: Vec<RawDataDiff> = Vec::new();
+    let mut offset = 0;
+    for edit in edits {
+        match (*edit, out.last_mut()) {
+            (Edit::Equal, Some(RawDataDiff::Equal(n))) => *n += 1,
+            (Edit::Equal, _) => out.push(RawDataDiff::Equal(1)),
+            (Edit::Insert(byte), Some(RawDataDiff::Insert { offset: at, bytes }))
+                if *at == offset =>
+            {
+                bytes.push(byte)
+            }
+            (Edit::Insert(byte), _) => out.push(RawDataDiff::Insert {
+                offset,
+                bytes: vec![byte],
+            }),
+            (Edit::Delete, Some(RawDataDiff::Delete { count, .. })) => *count += 1,
+            (Edit::Delete, _) => out.push(RawDataDiff::Delete { offset, count: 1 }),
+        }
+        if !matches!(edit, Edit::Insert(_)) {
+            offset += 1;
+        }
+    }
+    out
+}
+
+/// `=`/`-`/`+` annotated rendering of `diff(a, b)`, one run per line.
+pub(crate) fn diff_display(a: &[u8], b: &[u8]) -> String {
+    let mut out = String::new();
+    let mut offset = 0;
+    for run in diff(a, b) {
+        // Writing to a String cannot fail.
+        let _ = match &run {
+            RawDataDiff::Equal(n) => {
+                offset += n;
+                writeln!(out, "= {} bytes", n)
+            }
+            RawDataDiff::Delete { offset: at, count } => {
+                offset += count;
+                writeln!(out, "- @{} \"{}\"", at, a[*at..at + count].escape_ascii())
+            }
+            RawDataDiff::Insert { offset: at, bytes } => {
+                writeln!(out, "+ @{} \"{}\"", at, bytes.escape_ascii())
+            }
+        };
+    }
+    debug_assert_eq!(offset, a.len());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(base: &[u8], diff: &[RawDataDiff]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut pos = 0;
+        for run in diff {
+            match run {
+                RawDataDiff::Equal(n) => {
+                    out.extend_from_slice(&base[pos..pos + n]);
+                    pos += n;
+                }
+                RawDataDiff::Delete { offset, count } => {
+                    assert_eq!(*offset, pos);
+                    pos += count;
+                }
+                RawDataDiff::Insert { offset, bytes } => {
+                    assert_eq!(*offset, pos);
+                    out.extend_from_slice(bytes);
+                }
+            }
+        }
+        assert_eq!(pos, base.len());
+        out
+    }
+
+    #[test]
+    fn test_diff_reconstructs_other() {
+        let cases: [(&[u8], &[u8]); 6] = [
+            (b"", b""),
+            (b"", b"abc"),
+            (b"abc", b""),
+            (b"abcabba", b"cbabac"),
+            (b"GET /index HTTP/1.1", b"GET /idx HTTP/1.0\r\n"),
+            (b"\x00\x01\x02\x03", b"\x00\x02\x03\x04"),
+        ];
+        for (a, b) in cases {
+            assert_eq!(apply(a, &diff(a, b)), b);
+        }
+    }
+
+    #[test]
+    fn test_diff_of_disjoint_payloads_stays_bounded() {
+        let a = vec![b'a'; 5000];
+        let b: Vec<u8> = (0..4000u32).map(|i| b"xyz"[i as usize % 3]).collect();
+        let runs = diff(&a, &b);
+        assert_eq!(apply(&a, &runs), b);
+        assert_eq!(
+            runs,
+            [
+                RawDataDiff::Delete {
+                    offset: 0,
+                    count: 5000
+                },
+                RawDataDiff::Insert {
+                    offset: 5000,
+                    bytes: b.clone()
+                },
+            ]
+        );
+
+        // Small differences inside large payloads still get exact scripts.
+        let mut edited = a.clone();
+        edited[2500] = b'b';
+        assert_eq!(diff(&a, &edited).len(), 4);
+        for i in (0..edited.len()).step_by(50) {
+            edited[i] = b'b';
+        }
+        let runs = diff(&a, &edited);
+        assert_eq!(apply(&a, &runs), edited);
+        let edits: usize = runs
+            .iter()
+            .map(|run| match run {
+                RawDataDiff::Equal(_) => 0,
+                RawDataDiff::Delete { count, .. } => *count,
+                RawDataDiff::Insert { bytes, .. } => bytes.len(),
+            })
+            .sum();
+        assert_eq!(edits, 2 * 100);
+    }
+
+    #[test]
+    fn test_diff_runs_and_display() {
+        let runs = diff(b"key=old;", b"key=new;");
+        assert_eq!(
+            runs,
+            [
+                RawDataDiff::Equal(4),
+                RawDataDiff::Delete {
+                    offset: 4,
+                    count: 3
+                },
+                RawDataDiff::Insert {
+                    offset: 7,
+                    bytes: b"new".to_vec()
+                },
+                RawDataDiff::Equal(1),
+            ]
+        );
+        assert_eq!(diff(b"same", b"same"), [RawDataDiff::Equal(4)]);
+        assert_eq!(
+            diff_display(b"key=old;", b"key=new;"),
+            "= 4 bytes\n- @4 \"old\"\n+ @7 \"new\"\n= 1 bytes\n"
+        );
+    }
+}
@@ -18,6 +18,8 @@ pub enum DataErrKind {
     LessStc(String),
     #[error("define less : {0}")]
     LessDef(String),
+    #[error("out of bounds : {0}")]
+    OutOfBounds(String),
 }
 
 impl From<String> for DataErrKind {
@@ -26,23 +28,183 @@ impl From<String> for DataErrKind {
     }
 }
 
+use std::fmt;
+use std::sync::Arc;
+
 use derive_more::From;
+use orion_error::OperationContext;
 use orion_error::conversion::ToStructError;
-use orion_error::{OrionError, StructError, UnifiedReason};
+use orion_error::reason::DomainReason;
+use orion_error::{ErrorCode, ErrorIdentityProvider, StructError, UnifiedReason};
+use wp_model_core::raw::RawData;
 
-#[derive(Debug, Clone, PartialEq, Serialize, From, OrionError)]
+#[derive(Debug, Clone, Serialize, From, ErrorCode, ErrorIdentityProvider)]
 pub enum WparseReason {
-    #[orion_error(identity = "biz.plugin")]
+    #[orion_error(identity = "biz.plugin", code = 500)]
     #[from(skip)]
     Plugin(String),
-    #[orion_error(identity = "biz.not_match", message = "not match")]
-    NotMatch,
-    #[orion_error(identity = "biz.line_proc")]
+    /// Input is not in a format this parser handles, optionally saying why
+    /// (e.g. `"expected magic 0x1F8B, found 0x4D5A"`).
+    #[orion_error(identity = "biz.not_match", code = 500)]
+    #[from(skip)]
+    NotMatch(Option<String>),
+    #[orion_error(identity = "biz.line_proc", code = 500)]
     LineProc(String),
+    /// A pipeline step reported it cannot run (`can_process() == false`).
+    #[orion_error(identity = "conf.step_rejected", code = 400)]
+    #[from(skip)]
+    StepRejected(String),
+    /// A pipeline step failed while processing data.
+    #[orion_error(identity = "sys.step_failed", code = 500)]
+    #[from(skip)]
+    StepFailed(String),
+    /// Input or configuration is not valid for an encoding/transform step.
+    #[orion_error(identity = "biz.encoding", code = 500)]
+    #[from(skip)]
+    Encoding(String),
+    /// A processing stage exceeded its deadline; carries the stage name.
+    #[orion_error(identity = "sys.pipe_timeout", code = 500)]
+    #[from(skip)]
+    Timeout(String),
+    /// Input ended before a declared length was satisfied.
+    #[orion_error(identity = "biz.truncated", code = 500)]
+    #[from(skip)]
+    Truncated(String),
+    /// Valid input using a protocol feature this parser does not implement;
+    /// carries the feature name.
+    #[orion_error(identity = "biz.not_supported", code = 501)]
+    #[from(skip)]
+    NotSupported(String),
+    /// A payload digest did not match the expected value; carries the
+    /// algorithm name (e.g. `"sha256"`).
+    #[orion_error(identity = "biz.checksum_mismatch", code = 500)]
+    #[from(skip)]
+    ChecksumMismatch(String),
+    /// Domain-specific error from a plugin that fits no other variant.
+    ///
+    /// Displays as the wrapped error and serializes as its message. Two
+    /// `Custom` reasons are equal only if they share the same `Arc`.
+    #[orion_error(identity = "biz.custom", code = 599)]
+    #[from(skip)]
+    #[serde(serialize_with = "serialize_custom")]
+    Custom(Arc<dyn std::error::Error + Send + Sync + 'static>),
     #[orion_error(transparent)]
     Uvs(UnifiedReason),
 }
 
+macro_rules! unified_constructors {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("`WparseReason::Uvs(UnifiedReason::", stringify!($name), "())`.")]
+            pub fn $name() -> Self {
+                Self::Uvs(UnifiedReason::$name())
+            }
+        )*
+    };
+}
+
+impl WparseReason {
+    unified_constructors!(
+        core_conf,
+        feature_conf,
+        dynamic_conf,
+        validation_error,
+        business_error,
+        rule_error,
+        not_found_error,
+        permission_error,
+        data_error,
+        system_error,
+        network_error,
+        resource_error,
+        timeout_error,
+        external_error,
+        logic_error,
+    );
+
+    /// `NotMatch` without a description.
+    pub fn not_match() -> Self {
+        Self::NotMatch(None)
+    }
+
+    /// `NotMatch` explaining why the input was rejected.
+    pub fn not_match_because(why: impl Into<String>) -> Self {
+        Self::NotMatch(Some(why.into()))
+    }
+
+    /// [`WparseReason::NotSupported`] naming the unimplemented `feature`.
+    pub fn not_supported(feature: impl Into<String>) -> Self {
+        Self::NotSupported(feature.into())
+    }
+
+    /// Wrap an arbitrary error as [`WparseReason::Custom`].
+    pub fn custom<E>(err: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(err))
+    }
+
+    /// `to_err()` followed by [`WparseErrorExt::with_backtrace`], for errors
+    /// raised inside this crate.
+    pub(crate) fn into_traced_err(self) -> WparseError {
+        self.to_err().with_backtrace()
+    }
+}
+
+impl fmt::Display for WparseReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WparseReason::Plugin(_) => f.write_str("plugin"),
+            WparseReason::NotMatch(None) => f.write_str("not match"),
+            WparseReason::NotMatch(Some(why)) => write!(f, "not match: {}", why),
+            WparseReason::LineProc(_) => f.write_str("line proc"),
+            WparseReason::StepRejected(_) => f.write_str("step rejected"),
+            WparseReason::StepFailed(_) => f.write_str("step failed"),
+            WparseReason::Encoding(_) => f.write_str("encoding"),
+            WparseReason::Timeout(_) => f.write_str("pipe timeout"),
+            WparseReason::Truncated(_) => f.write_str("truncated"),
+            WparseReason::NotSupported(feature) => write!(f, "not supported: {}", feature),
+            WparseReason::ChecksumMismatch(algo) => write!(f, "checksum mismatch: {}", algo),
+            WparseReason::Custom(err) => f.write_str(&err.to_string()),
+            WparseReason::Uvs(reason) => fmt::Display::fmt(reason, f),
+        }
+    }
+}
+
+impl PartialEq for WparseReason {
+    fn eq(&self, other: &Self) -> bool {
+        use WparseReason::*;
+        match (self, other) {
+            (Plugin(a), Plugin(b))
+            | (LineProc(a), LineProc(b))
+            | (StepRejected(a), StepRejected(b))
+            | (StepFailed(a), StepFailed(b))
+            | (Encoding(a), Encoding(b))
+            | (Timeout(a), Timeout(b))
+            | (Truncated(a), Truncated(b))
+            | (NotSupported(a), NotSupported(b))
+            | (ChecksumMismatch(a), ChecksumMismatch(b)) => a == b,
+            (NotMatch(a), NotMatch(b)) => a == b,
+            (Custom(a), Custom(b)) => Arc::ptr_eq(a, b),
+            (Uvs(a), Uvs(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl DomainReason for WparseReason {}
+
+fn serialize_custom<S>(
+    err: &Arc<dyn std::error::Error + Send + Sync + 'static>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_str(err)
+}
+
 pub type WparseError = StructError<WparseReason>;
 
 impl From<DataErrKind> for WparseError {
@@ -50,10 +212,243 @@ impl From<DataErrKind> for WparseError {
         WparseReason::data_error()
             .to_err()
             .with_detail(format!("{}", value))
+            .with_backtrace()
     }
 }
 pub type WparseResult<T> = Result<T, WparseError>;
 
+/// A call stack stored as the source of a [`WparseError`], where it stays
+/// out of the error's `Display` output.
+#[cfg(feature = "backtrace")]
+#[derive(Debug)]
+struct CapturedBacktrace(String);
+
+#[cfg(feature = "backtrace")]
+impl fmt::Display for CapturedBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("captured backtrace")
+    }
+}
+
+#[cfg(feature = "backtrace")]
+impl std::error::Error for CapturedBacktrace {}
+
+/// Extension methods for [`WparseError`].
+///
+/// `WparseError` is an alias of `orion_error::StructError`, so crate-specific
+/// helpers live on this trait instead of an inherent impl.
+pub trait WparseErrorExt: Sized {
+    /// Capture the current call stack and attach it to the error.
+    ///
+    /// Only active with the `backtrace` feature and when `RUST_BACKTRACE` /
+    /// `RUST_LIB_BACKTRACE` enables capturing; otherwise this returns `self`
+    /// untouched. The trace is kept as the error's source, so it does not
+    /// show up in `Display`, and an error that already has a source is left
+    /// as is. Errors raised by this crate's processors and helpers, and
+    /// those converted from `DataErrKind`, go through it.
+    fn with_backtrace(self) -> Self;
+
+    /// The backtrace captured by [`with_backtrace`](Self::with_backtrace), if any.
+    fn backtrace(&self) -> Option<&str>;
+
+    /// Combine this error with `next`, e.g. when every alternative failed.
+    fn chain(self, next: WparseError) -> WparseErrors;
+
+    /// Whether retrying the same operation may succeed.
+    ///
+    /// True for timeouts and for transient infrastructure reasons (network,
+    /// system, resource, external); everything else, including
+    /// `NotSupported`, is deterministic.
+    fn is_retriable(&self) -> bool;
+
+    /// Whether the reason is [`WparseReason::NotSupported`].
+    fn is_not_supported(&self) -> bool;
+
+    /// A `FormatError` data error for `data`, whose detail carries `msg` and
+    /// a hex preview of at most the first [`PREVIEW_LEN`] bytes.
+    ///
+    /// With the `redact` feature the preview only states the payload length.
+    fn format_error_with_preview(data: &RawData, msg: impl Into<String>) -> Self;
+
+    /// The error wrapped by a [`WparseReason::Custom`] reason, if it is an `E`.
+    ///
+    /// `None` for every other reason, like `Box<dyn Error>::downcast_ref`.
+    /// There is no `downcast_reason_mut`: `StructError` only lends its reason
+    /// through `reason(&self)`, and `Custom` holds the error in a shared
+    /// `Arc`.
+    fn downcast_reason<E: std::error::Error + 'static>(&self) -> Option<&E>;
+
+    /// Whether the reason is a `Custom` wrapping an `E`.
+    fn is_reason<E: std::error::Error + 'static>(&self) -> bool {
+        self.downcast_reason::<E>().is_some()
+    }
+}
+
+/// Maximum number of payload bytes shown by
+/// [`WparseErrorExt::format_error_with_preview`].
+pub const PREVIEW_LEN: usize = 32;
+
+#[cfg(not(feature = "redact"))]
+fn data_preview(data: &RawData) -> String {
+    use std::fmt::Write;
+
+    let bytes = data.as_bytes();
+    let shown = &bytes[..bytes.len().min(PREVIEW_LEN)];
+    let mut out = String::from("preview:");
+    for b in shown {
+        let _ = write!(out, " {:02x}", b);
+    }
+    if bytes.len() > shown.len() {
+        let _ = write!(out, " (+{} bytes)", bytes.len() - shown.len());
+    }
+    out
+}
+
+#[cfg(feature = "redact")]
+fn data_preview(data: &RawData) -> String {
+    format!("preview redacted ({} bytes)", data.len())
+}
+
+impl WparseErrorExt for WparseError {
+    #[cfg(feature = "backtrace")]
+    fn with_backtrace(self) -> Self {
+        use std::backtrace::{Backtrace, BacktraceStatus};
+
+        if self.source_ref().is_some() {
+            return self;
+        }
+        let bt = Backtrace::capture();
+        if bt.status() != BacktraceStatus::Captured {
+            return self;
+        }
+        self.with_source(CapturedBacktrace(bt.to_string()))
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    #[inline]
+    fn with_backtrace(self) -> Self {
+        self
+    }
+
+    #[cfg(feature = "backtrace")]
+    fn backtrace(&self) -> Option<&str> {
+        self.source_ref()
+            .and_then(|source| source.downcast_ref::<CapturedBacktrace>())
+            .map(|bt| bt.0.as_str())
+    }
+
+    #[cfg(not(feature = "backtrace"))]
+    #[inline]
+    fn backtrace(&self) -> Option<&str> {
+        None
+    }
+
+    fn chain(self, next: WparseError) -> WparseErrors {
+        WparseErrors(vec![self, next])
+    }
+
+    fn is_retriable(&self) -> bool {
+        match self.reason() {
+            WparseReason::Timeout(_) => true,
+            WparseReason::Uvs(reason) => reason.is_retryable(),
+            _ => false,
+        }
+    }
+
+    fn is_not_supported(&self) -> bool {
+        matches!(self.reason(), WparseReason::NotSupported(_))
+    }
+
+    fn format_error_with_preview(data: &RawData, msg: impl Into<String>) -> Self {
+        DataErrKind::FormatError(msg.into(), Some(data_preview(data))).into()
+    }
+
+    fn downcast_reason<E: std::error::Error + 'static>(&self) -> Option<&E> {
+        match self.reason() {
+            WparseReason::Custom(err) => err.downcast_ref::<E>(),
+            _ => None,
+        }
+    }
+}
+
+/// Attach plugin context to the error of a [`WparseResult`] in `?` chains:
+/// `decode(data).with_plugin(self.name())?`.
+///
+/// Each call adds an operation context whose field shows up in the error's
+/// `Display` output; `Ok` values pass through untouched.
+pub trait WparseResultExt: Sized {
+    /// Record the plugin or processor that was running.
+    fn with_plugin(self, name: impl fmt::Display) -> Self;
+
+    /// Record the byte offset in the input where the failure occurred.
+    fn with_offset(self, offset: usize) -> Self;
+}
+
+impl<T> WparseResultExt for WparseResult<T> {
+    fn with_plugin(self, name: impl fmt::Display) -> Self {
+        self.map_err(|err| err.with_context(OperationContext::new().with_field("plugin", name)))
+    }
+
+    fn with_offset(self, offset: usize) -> Self {
+        self.map_err(|err| err.with_context(OperationContext::new().with_field("offset", offset)))
+    }
+}
+
+/// Several errors reported together, in the order they occurred.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WparseErrors(pub Vec<WparseError>);
+
+impl WparseErrors {
+    pub fn push(mut self, err: WparseError) -> Self {
+        self.0.push(err);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, WparseError> {
+        self.0.iter()
+    }
+
+    /// The first collected error, for callers that can only propagate one.
+    pub fn into_first(self) -> Option<WparseError> {
+        self.0.into_iter().next()
+    }
+}
+
+impl From<Vec<WparseError>> for WparseErrors {
+    fn from(errors: Vec<WparseError>) -> Self {
+        WparseErrors(errors)
+    }
+}
+
+impl std::fmt::Display for WparseErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} error(s):", self.0.len())?;
+        for (i, err) in self.0.iter().enumerate() {
+            write!(f, "\n[{}] {}", i + 1, err)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for WparseErrors {}
+
+/// `Ok(())` when `errors` is empty, otherwise all of them as one [`WparseErrors`].
+pub fn all_of(errors: Vec<WparseError>) -> Result<(), WparseErrors> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(WparseErrors(errors))
+    }
+}
+
 /// 兼容别名：保留历史命名，方便渐进迁移。
 #[deprecated(note = "use `WparseReason` instead")]
 pub type WplParseReason = WparseReason;
@@ -63,3 +458,170 @@ pub type WplParseError = WparseError;
 
 #[deprecated(note = "use `WparseResult` instead")]
 pub type WplParseResult<T> = WparseResult<T>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::backtrace::{Backtrace, BacktraceStatus};
+
+    #[test]
+    fn test_result_ext_attaches_context_on_err_only() {
+        let ok: WparseResult<u8> = Ok(1);
+        assert_eq!(ok.with_plugin("strip").with_offset(3).unwrap(), 1);
+
+        let err: WparseResult<u8> = Err(DataErrKind::LessData.into());
+        let err = err.with_plugin("strip").with_offset(3).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::data_error());
+        assert!(err.to_string().contains("offset: 3"));
+        assert!(err.to_string().contains("plugin: strip"));
+    }
+
+    #[test]
+    fn test_format_error_with_preview_is_bounded() {
+        let data = RawData::Bytes(bytes::Bytes::from(vec![0xabu8; 100]));
+        let err = WparseError::format_error_with_preview(&data, "bad header");
+        assert_eq!(err.reason(), &WparseReason::data_error());
+
+        let detail = err.detail().clone().unwrap();
+        assert!(detail.contains("bad header"));
+        if cfg!(feature = "redact") {
+            assert!(detail.contains("redacted (100 bytes)"));
+            assert!(!detail.contains("ab"));
+        } else {
+            assert_eq!(detail.matches("ab").count(), PREVIEW_LEN);
+            assert!(detail.contains("(+68 bytes)"));
+        }
+    }
+
+    #[test]
+    fn test_backtrace_follows_feature_and_env() {
+        let err: WparseError = DataErrKind::LessData.into();
+        let capturing = cfg!(feature = "backtrace")
+            && Backtrace::capture().status() == BacktraceStatus::Captured;
+        assert_eq!(err.backtrace().is_some(), capturing);
+        assert_eq!(err.reason(), &WparseReason::data_error());
+        assert!(!err.to_string().contains("backtrace"));
+
+        let internal = WparseReason::Timeout("dns".into()).into_traced_err();
+        assert_eq!(internal.backtrace().is_some(), capturing);
+        assert_eq!(
+            internal.to_string(),
+            WparseReason::Timeout("dns".into()).to_err().to_string()
+        );
+
+        let wrapped = WparseReason::StepFailed("step".into())
+            .to_err()
+            .with_source(err)
+            .with_backtrace();
+        assert!(wrapped.backtrace().is_none());
+    }
+
+    #[test]
+    fn test_chain_collects_errors() {
+        let first = WparseReason::Plugin("no magic".into()).to_err();
+        let second = WparseReason::LineProc("bad length".into()).to_err();
+        let errors = first.clone().chain(second);
+        assert_eq!(errors.len(), 2);
+
+        let text = errors.to_string();
+        assert!(text.contains("[1] plugin"));
+        assert!(text.contains("[2] line proc"));
+
+        let errors = errors.push(WparseReason::not_match().to_err());
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors.into_first(), Some(first));
+    }
+
+    #[test]
+    fn test_all_of() {
+        assert!(all_of(Vec::new()).is_ok());
+        let errors = all_of(vec![WparseReason::not_match().to_err()]).unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_is_retriable() {
+        assert!(WparseReason::Timeout("dns".into()).to_err().is_retriable());
+        assert!(WparseReason::network_error().to_err().is_retriable());
+        assert!(!WparseReason::data_error().to_err().is_retriable());
+        assert!(!WparseReason::not_match().to_err().is_retriable());
+    }
+
+    #[test]
+    fn test_timeout_reason_identity() {
+        use orion_error::reason::{ErrorCategory, ErrorIdentityProvider};
+
+        let reason = WparseReason::Timeout("remote_lookup".into());
+        assert_eq!(reason.to_string(), "pipe timeout");
+        assert_eq!(reason.stable_code(), "sys.pipe_timeout");
+        assert_eq!(reason.error_category(), ErrorCategory::Sys);
+    }
+
+    #[test]
+    fn test_not_match_display_includes_reason() {
+        assert_eq!(WparseReason::not_match().to_string(), "not match");
+        let reason = WparseReason::not_match_because("expected magic 0x1F8B, found 0x4D5A");
+        assert_eq!(
+            reason.to_string(),
+            "not match: expected magic 0x1F8B, found 0x4D5A"
+        );
+        assert_ne!(reason, WparseReason::not_match());
+
+        let errors = WparseReason::not_match_because("not gzip")
+            .to_err()
+            .chain(WparseReason::not_match_because("not zstd").to_err());
+        assert!(errors.to_string().contains("[2] not match: not zstd"));
+    }
+
+    #[test]
+    fn test_not_supported_reason() {
+        use orion_error::reason::ErrorCode;
+
+        let reason = WparseReason::not_supported("SMB3 compression");
+        assert_eq!(reason.to_string(), "not supported: SMB3 compression");
+        assert_eq!(reason.error_code(), 501);
+
+        let err = reason.to_err();
+        assert!(err.is_not_supported());
+        assert!(!err.is_retriable());
+        assert!(!WparseReason::not_match().to_err().is_not_supported());
+    }
+
+    #[test]
+    fn test_custom_reason_wraps_io_error() {
+        use orion_error::reason::ErrorCode;
+        use std::io;
+
+        let reason = WparseReason::custom(io::Error::new(io::ErrorKind::NotFound, "no spool dir"));
+        assert_eq!(reason.to_string(), "no spool dir");
+        assert_eq!(reason.error_code(), 599);
+        assert_eq!(reason, reason.clone());
+        assert_ne!(
+            reason,
+            WparseReason::custom(io::Error::new(io::ErrorKind::NotFound, "no spool dir"))
+        );
+        assert_ne!(reason, WparseReason::not_match());
+        assert_eq!(
+            serde_json::to_string(&reason).unwrap(),
+            r#"{"Custom":"no spool dir"}"#
+        );
+
+        let err = reason.to_err();
+        assert!(err.to_string().contains("no spool dir"));
+    }
+
+    #[test]
+    fn test_downcast_custom_reason() {
+        use std::io;
+
+        let err = WparseReason::custom(io::Error::new(io::ErrorKind::NotFound, "gone")).to_err();
+        let io_err = err.downcast_reason::<io::Error>().unwrap();
+        assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+        assert!(err.is_reason::<io::Error>());
+        assert!(err.downcast_reason::<std::fmt::Error>().is_none());
+        assert!(!err.is_reason::<std::fmt::Error>());
+
+        let plain: WparseError = DataErrKind::LessData.into();
+        assert!(plain.downcast_reason::<io::Error>().is_none());
+    }
+}
@@ -0,0 +1,90 @@
+//! `xxd`-style hex dumps of [`RawData`] payloads.
+
+use std::fmt;
+
+use wp_model_core::raw::RawData;
+
+const BYTES_PER_LINE: usize = 16;
+/// `"00000000: "` + 8 groups of `"xxxx "` + gutter padding + 16 ASCII + `'\n'`.
+const LINE_WIDTH: usize = 10 + 40 + 1 + BYTES_PER_LINE + 1;
+
+/// Formatting wrapper over a payload, returned by
+/// [`RawDataExt::hex_view`](crate::RawDataExt::hex_view).
+///
+/// `Display` and alternate `Debug` (`{:#?}`) render the hex dump; plain
+/// `Debug` defers to `RawData`'s own `Debug`.
+#[derive(Clone, Copy)]
+pub struct HexDump<'a>(pub &'a RawData);
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_hexdump(f, self.0.as_bytes())
+    }
+}
+
+impl fmt::Debug for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write_hexdump(f, self.0.as_bytes())
+        } else {
+            fmt::Debug::fmt(self.0, f)
+        }
+    }
+}
+
+/// Render `bytes` as an `xxd` dump into an exactly pre-sized `String`.
+pub(crate) fn hexdump(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(BYTES_PER_LINE) * LINE_WIDTH);
+    write_hexdump(&mut out, bytes).expect("writing to a String cannot fail");
+    out
+}
+
+fn write_hexdump(out: &mut impl fmt::Write, bytes: &[u8]) -> fmt::Result {
+    for (line, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        write!(out, "{:08x}:", line * BYTES_PER_LINE)?;
+        for (i, byte) in chunk.iter().enumerate() {
+            if i % 2 == 0 {
+                out.write_char(' ')?;
+            }
+            write!(out, "{:02x}", byte)?;
+        }
+        // Pad short final lines so the ASCII gutter stays aligned.
+        let missing = BYTES_PER_LINE - chunk.len();
+        let pad = missing * 2 + missing / 2;
+        write!(out, "{:pad$}  ", "")?;
+        for &byte in chunk {
+            let printable = byte.is_ascii_graphic() || byte == b' ';
+            out.write_char(if printable { byte as char } else { '.' })?;
+        }
+        out.write_char('\n')?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hexdump_matches_xxd_layout() {
+        let mut data = b"Hello, world!\n".to_vec();
+        data.extend([0x00, 0xff, 0x7f, b'A']);
+        let dump = hexdump(&data);
+        assert_eq!(
+            dump,
+            "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a 00ff  Hello, world!...\n\
+             00000010: 7f41                                     .A\n"
+        );
+        assert!(dump.capacity() >= dump.len());
+        assert!(dump.capacity() <= 2 * LINE_WIDTH);
+        assert_eq!(hexdump(b""), "");
+    }
+
+    #[test]
+    fn test_hexdump_alternate_debug() {
+        let raw = RawData::from_string("ab");
+        let view = HexDump(&raw);
+        assert_eq!(format!("{:#?}", view), format!("{}", view));
+        assert_eq!(format!("{:?}", view), format!("{:?}", raw));
+    }
+}
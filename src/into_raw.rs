@@ -0,0 +1,134 @@
+//! Conversion traits for handing domain types to pipelines as [`RawData`].
+
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use wp_model_core::raw::RawData;
+
+use crate::RawDataBuilder;
+
+/// Types with a raw payload representation, consumed on conversion.
+///
+/// Implement this for domain types (a parsed syslog message, a protocol
+/// frame) to pass them straight to [`Pipeline::run_typed`].
+///
+/// `RawData` is defined in `wp-model-core`, so `From<T> for RawData` cannot be
+/// added for std or `bytes` types here; this trait covers them instead.
+///
+/// [`Pipeline::run_typed`]: crate::Pipeline::run_typed
+pub trait IntoRawData {
+    fn into_raw_data(self) -> RawData;
+}
+
+/// Types that can produce a raw payload from a shared reference, cloning
+/// or copying as needed.
+pub trait AsRawData {
+    fn as_raw_data(&self) -> RawData;
+}
+
+impl IntoRawData for RawData {
+    fn into_raw_data(self) -> RawData {
+        self
+    }
+}
+
+impl IntoRawData for String {
+    fn into_raw_data(self) -> RawData {
+        RawData::String(self)
+    }
+}
+
+impl IntoRawData for &str {
+    fn into_raw_data(self) -> RawData {
+        RawData::from_string(self)
+    }
+}
+
+impl IntoRawData for Vec<u8> {
+    fn into_raw_data(self) -> RawData {
+        RawData::Bytes(Bytes::from(self))
+    }
+}
+
+impl IntoRawData for &[u8] {
+    fn into_raw_data(self) -> RawData {
+        RawData::Bytes(Bytes::copy_from_slice(self))
+    }
+}
+
+impl IntoRawData for Bytes {
+    fn into_raw_data(self) -> RawData {
+        RawData::Bytes(self)
+    }
+}
+
+impl IntoRawData for BytesMut {
+    fn into_raw_data(self) -> RawData {
+        RawData::Bytes(self.freeze())
+    }
+}
+
+impl IntoRawData for Arc<Vec<u8>> {
+    fn into_raw_data(self) -> RawData {
+        RawData::from_arc_bytes(self)
+    }
+}
+
+impl IntoRawData for RawDataBuilder {
+    fn into_raw_data(self) -> RawData {
+        self.build()
+    }
+}
+
+impl AsRawData for RawData {
+    fn as_raw_data(&self) -> RawData {
+        self.clone()
+    }
+}
+
+impl AsRawData for str {
+    fn as_raw_data(&self) -> RawData {
+        RawData::from_string(self)
+    }
+}
+
+impl AsRawData for String {
+    fn as_raw_data(&self) -> RawData {
+        RawData::from_string(self.as_str())
+    }
+}
+
+impl AsRawData for [u8] {
+    fn as_raw_data(&self) -> RawData {
+        RawData::Bytes(Bytes::copy_from_slice(self))
+    }
+}
+
+impl AsRawData for Vec<u8> {
+    fn as_raw_data(&self) -> RawData {
+        self.as_slice().as_raw_data()
+    }
+}
+
+impl AsRawData for Bytes {
+    fn as_raw_data(&self) -> RawData {
+        RawData::Bytes(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_conversions() {
+        assert!(matches!("ab".into_raw_data(), RawData::String(s) if s == "ab"));
+        assert_eq!(vec![1u8, 2].into_raw_data().as_bytes(), [1, 2]);
+        assert!(Arc::new(vec![7u8]).into_raw_data().is_zero_copy());
+
+        let bytes = Bytes::from_static(b"xyz");
+        let raw = bytes.as_raw_data();
+        assert_eq!(raw.as_bytes().as_ptr(), bytes.as_ptr());
+        assert_eq!("s".to_string().as_raw_data().as_bytes(), b"s");
+    }
+}
@@ -6,18 +6,106 @@ use std::sync::Arc;
 
 use wp_model_core::model::DataRecord;
 
+mod arena;
+pub use arena::RawDataArena;
+mod base64;
+mod checksum;
+pub use checksum::Checksum;
+#[cfg(feature = "codec")]
+mod codec;
+#[cfg(feature = "codec")]
+pub use codec::{RawDataDecoder, RawDataEncoder};
+#[cfg(feature = "compression")]
+mod compression;
+mod context;
+pub use context::ParseContext;
+mod csv;
+pub use csv::parse_csv_line;
+mod diff;
+pub use diff::RawDataDiff;
 mod error;
-pub use error::{WparseError, WparseReason, WparseResult};
+pub use error::{
+    PREVIEW_LEN, WparseError, WparseErrorExt, WparseErrors, WparseReason, WparseResult,
+    WparseResultExt, all_of,
+};
 #[allow(deprecated)]
 pub use error::{WplParseError, WplParseReason, WplParseResult};
 use wp_model_core::raw::RawData;
+
+mod hexdump;
+pub use hexdump::HexDump;
+mod into_raw;
+pub use into_raw::{AsRawData, IntoRawData};
+mod metrics;
+pub use metrics::{MetricsCollectingPipeline, PipelineStats, ProcessorMetrics, StepStat};
+mod percent;
+mod pipeline;
+pub use pipeline::{PipeObserver, Pipeline};
+pub mod processors;
+mod raw_builder;
+pub use raw_builder::RawDataBuilder;
+mod raw_ext;
+pub use raw_ext::RawDataExt;
+mod raw_writer;
+pub use raw_writer::RawDataWriter;
+mod reconfigurable;
+pub use reconfigurable::ReconfigurablePipeline;
+mod record_ext;
+pub use record_ext::{DataRecordRawExt, parse_kv};
+mod registry;
+#[cfg(feature = "serde")]
+pub mod serde_base64;
+pub use registry::{PipeProcessorRegistry, PipelineRegistry, ProcessorBuilder, required_config};
+pub mod testing;
 // Re-export necessary types from wp-lang that we still need
 
+/// Successful output of a parser: the parsed record plus any unconsumed input.
+#[derive(Debug, Clone)]
+pub struct ParsedFrame {
+    pub record: DataRecord,
+    pub remaining: RawData,
+}
+
+impl ParsedFrame {
+    pub fn new(record: DataRecord, remaining: RawData) -> Self {
+        Self { record, remaining }
+    }
+
+    pub fn record(&self) -> &DataRecord {
+        &self.record
+    }
+
+    pub fn remaining(&self) -> &RawData {
+        &self.remaining
+    }
+
+    /// Split into `(record, remaining)`.
+    pub fn into_parts(self) -> (DataRecord, RawData) {
+        (self.record, self.remaining)
+    }
+}
+
+impl From<(DataRecord, RawData)> for ParsedFrame {
+    fn from((record, remaining): (DataRecord, RawData)) -> Self {
+        Self::new(record, remaining)
+    }
+}
+
+impl From<ParsedFrame> for (DataRecord, RawData) {
+    fn from(frame: ParsedFrame) -> Self {
+        frame.into_parts()
+    }
+}
+
 /// Result type for plugin parsing operations.
 ///
-/// On success, returns a tuple of `(DataRecord, remaining_raw)`.
+/// On success, returns a [`ParsedFrame`] holding the record and the remaining raw data.
 /// On failure, returns a WparseError (旧名称 `WplParseError` 仍可用，但已弃用)。
-pub type DataResult = Result<(DataRecord, RawData), WparseError>;
+pub type DataResult = Result<ParsedFrame, WparseError>;
+
+/// 兼容别名：旧版基于元组的解析结果。
+#[deprecated(note = "use `DataResult` with `ParsedFrame` instead")]
+pub type LegacyDataResult = Result<(DataRecord, RawData), WparseError>;
 
 /// Trait for pipeline data processing operations.
 ///
@@ -42,16 +130,177 @@ pub trait PipeProcessor {
     /// # Returns
     /// A string slice representing the processor name
     fn name(&self) -> &'static str;
+
+    /// Whether this processor is configured well enough to run at all.
+    ///
+    /// Used by [`Pipeline::dry_run`] to separate configuration problems from
+    /// runtime failures. Defaults to `true`.
+    fn can_process(&self) -> bool {
+        true
+    }
+
+    /// Whether `process` depends only on its input and configuration, with
+    /// no side effects, so its result may be cached or recomputed freely.
+    ///
+    /// Defaults to `false`; stateless built-ins override it.
+    fn is_pure(&self) -> bool {
+        false
+    }
+
+    /// Whether applying the processor to its own output changes nothing
+    /// (`p(p(x)) == p(x)`). Defaults to `false`.
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+
+    /// Whether empty input is a valid argument to `process`, rather than an
+    /// error such as `LessData`. Defaults to `true`.
+    ///
+    /// [`Pipeline::run_nonempty`] and [`Pipeline::run_if_nonempty`] screen
+    /// empty input out before any step runs.
+    fn accepts_empty(&self) -> bool {
+        true
+    }
+
+    /// Change a configuration parameter of a live processor.
+    ///
+    /// Keys a processor does not know are ignored; the default ignores all
+    /// of them. Invalid values should fail with a configuration error. Steps
+    /// shared as [`PipeHold`] cannot be mutated, so reconfigure them through
+    /// a [`ReconfigurablePipeline`] or by replacing the step.
+    fn set_config(&mut self, key: &str, value: &str) -> WparseResult<()> {
+        let _ = (key, value);
+        Ok(())
+    }
+
+    /// Current value of a configuration parameter, `None` for unknown keys
+    /// (the default).
+    fn get_config(&self, key: &str) -> Option<String> {
+        let _ = key;
+        None
+    }
+
+    /// Canonical key (name plus configuration) identifying equivalent
+    /// stateless processors.
+    ///
+    /// [`Pipeline::dedup_adjacent`] drops consecutive steps with equal keys;
+    /// processors returning `None` (the default) are never deduplicated.
+    fn identity_key(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether this step hands its input on unchanged, like the taps added
+    /// by [`Pipeline::inspect`]. [`Pipeline::type_check`] and
+    /// [`Pipeline::dedup_adjacent`] look through such steps. Defaults to
+    /// `false`.
+    fn is_passthrough(&self) -> bool {
+        false
+    }
+
+    /// Like [`process`](Self::process), with access to the context shared
+    /// by all steps of a [`Pipeline::run_with`] call.
+    ///
+    /// The default ignores `ctx` and calls `process`. Override it to read
+    /// typed extensions or add fields to the record being built.
+    fn process_with(&self, data: RawData, ctx: &ParseContext) -> WparseResult<RawData> {
+        let _ = ctx;
+        self.process(data)
+    }
+
+    /// Transform `data` in place.
+    ///
+    /// The default moves the payload out, calls [`process`](Self::process)
+    /// and stores the result, leaving `data` empty if it fails. Processors
+    /// that can rewrite a buffer without reallocating (e.g. byte masks over
+    /// a uniquely owned `ArcBytes`) override it; [`Pipeline::run_in_place`]
+    /// goes through this method.
+    fn process_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+        let input = std::mem::replace(data, RawData::Bytes(bytes::Bytes::new()));
+        *data = self.process(input)?;
+        Ok(())
+    }
+
+    /// Like [`process`](Self::process), but may split off a remainder that
+    /// did not fit this step's output (e.g. a paging or framing processor).
+    ///
+    /// The default calls `process` and returns no remainder; splitting
+    /// processors override it. [`Pipeline::run_split`] collects remainders.
+    fn process_split(&self, data: RawData) -> WparseResult<(RawData, Option<RawData>)> {
+        self.process(data).map(|out| (out, None))
+    }
+
+    /// Hook for adding processor-specific measurements to this step's
+    /// counters; called by [`MetricsCollectingPipeline`] after each call.
+    /// Defaults to a no-op.
+    fn record_metrics(&self, _metrics: &mut ProcessorMetrics) {}
+
+    /// Variant this processor expects as input, if it has a preference.
+    ///
+    /// Purely informational; [`Pipeline::type_check`] compares it with the
+    /// previous step's [`output_type_hint`](Self::output_type_hint).
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        None
+    }
+
+    /// Variant this processor always produces, if fixed.
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        None
+    }
+}
+
+/// Payload-free discriminant of [`RawData`], used for processor type hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RawDataKind {
+    String,
+    Bytes,
+    ArcBytes,
+}
+
+impl RawDataKind {
+    pub fn of(data: &RawData) -> Self {
+        match data {
+            RawData::String(_) => RawDataKind::String,
+            RawData::Bytes(_) => RawDataKind::Bytes,
+            RawData::ArcBytes(_) => RawDataKind::ArcBytes,
+        }
+    }
+
+    /// `Bytes` and `ArcBytes`, which hint-compatible processors treat alike.
+    pub fn is_binary(self) -> bool {
+        !matches!(self, RawDataKind::String)
+    }
+}
+
+impl std::fmt::Display for RawDataKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
 }
 
 pub type PipeHold = Arc<dyn PipeProcessor + Send + Sync>;
 
 #[cfg(test)]
 mod tests {
-    use super::RawData;
+    use super::{DataRecord, ParsedFrame, RawData};
     use bytes::Bytes;
     use std::sync::Arc;
 
+    #[test]
+    fn parsed_frame_converts_from_and_into_tuple() {
+        let frame = ParsedFrame::new(DataRecord::default(), RawData::from_string("rest"));
+        assert!(frame.record().is_empty());
+        assert_eq!(frame.remaining().as_bytes(), b"rest");
+
+        let (record, remaining): (DataRecord, RawData) = frame.into();
+        assert!(record.is_empty());
+        assert_eq!(remaining.as_bytes(), b"rest");
+
+        let frame = ParsedFrame::from((record, remaining));
+        let ParsedFrame { record, remaining } = frame;
+        assert!(record.is_empty());
+        assert_eq!(remaining.as_bytes(), b"rest");
+    }
+
     #[test]
     fn rawdata_as_bytes_and_len_cover_all_variants() {
         let text = RawData::from_string("hello");
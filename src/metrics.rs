@@ -0,0 +1,169 @@
+//! Per-step counters for pipelines running in production.
+
+use std::time::Instant;
+
+use wp_model_core::raw::RawData;
+
+use crate::{Pipeline, WparseResult};
+
+/// Accumulated counters for one pipeline step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessorMetrics {
+    pub calls: u64,
+    pub errors: u64,
+    pub input_bytes_total: u64,
+    /// Output bytes of successful calls only.
+    pub output_bytes_total: u64,
+    pub duration_nanos_total: u64,
+}
+
+impl ProcessorMetrics {
+    /// Fraction of calls that failed, `0.0` before the first call.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+
+    /// Mean wall-clock time per call in milliseconds, `0.0` before the first call.
+    pub fn avg_latency_ms(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.duration_nanos_total as f64 / self.calls as f64 / 1_000_000.0
+        }
+    }
+}
+
+/// Timing and sizes of one step of a [`Pipeline::run_with_stats`] call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StepStat {
+    pub name: String,
+    pub duration_ns: u64,
+    pub input_len: usize,
+    /// Zero when the step failed.
+    pub output_len: usize,
+}
+
+/// Per-step timings of a single run, in step order. A failed run lists the
+/// steps up to and including the failing one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PipelineStats {
+    pub steps: Vec<StepStat>,
+}
+
+impl PipelineStats {
+    pub fn total_duration_ns(&self) -> u64 {
+        self.steps.iter().map(|step| step.duration_ns).sum()
+    }
+
+    /// Sum of every step's input length.
+    pub fn total_input_bytes(&self) -> usize {
+        self.steps.iter().map(|step| step.input_len).sum()
+    }
+}
+
+/// Runs a [`Pipeline`] while timing each step and accumulating
+/// [`ProcessorMetrics`] per step.
+///
+/// After the built-in counters are updated, each processor's
+/// [`record_metrics`](crate::PipeProcessor::record_metrics) hook is called
+/// with its step's metrics. Steps after a failing one are not run and their
+/// counters are left untouched.
+#[derive(Debug, Clone)]
+pub struct MetricsCollectingPipeline {
+    pipeline: Pipeline,
+    metrics: Vec<ProcessorMetrics>,
+}
+
+impl MetricsCollectingPipeline {
+    pub fn new(pipeline: Pipeline) -> Self {
+        let metrics = vec![ProcessorMetrics::default(); pipeline.len()];
+        Self { pipeline, metrics }
+    }
+
+    pub fn pipeline(&self) -> &Pipeline {
+        &self.pipeline
+    }
+
+    /// Metrics in step order, one entry per step.
+    pub fn metrics(&self) -> &[ProcessorMetrics] {
+        &self.metrics
+    }
+
+    pub fn reset(&mut self) {
+        self.metrics.fill(ProcessorMetrics::default());
+    }
+
+    pub fn run(&mut self, input: RawData) -> WparseResult<RawData> {
+        let mut data = input;
+        for (index, (step, metrics)) in self
+            .pipeline
+            .steps()
+            .iter()
+            .zip(&mut self.metrics)
+            .enumerate()
+        {
+            metrics.calls += 1;
+            metrics.input_bytes_total += data.len() as u64;
+            let started = Instant::now();
+            let result = step.process(data);
+            metrics.duration_nanos_total += started.elapsed().as_nanos() as u64;
+            match &result {
+                Ok(out) => metrics.output_bytes_total += out.len() as u64,
+                Err(_) => metrics.errors += 1,
+            }
+            step.record_metrics(metrics);
+            data = self.pipeline.check_output(index, step, result?)?;
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::Strip;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_metrics_count_calls_and_bytes() {
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(2, 0)));
+        let mut collecting = MetricsCollectingPipeline::new(pipeline);
+
+        assert_eq!(
+            collecting
+                .run(RawData::from_string("abcd"))
+                .unwrap()
+                .as_bytes(),
+            b"d"
+        );
+        assert!(collecting.run(RawData::from_string("ab")).is_err());
+
+        let [first, second] = collecting.metrics() else {
+            panic!("one metrics entry per step");
+        };
+        assert_eq!(
+            (
+                first.calls,
+                first.input_bytes_total,
+                first.output_bytes_total
+            ),
+            (2, 6, 4)
+        );
+        assert_eq!(
+            (second.calls, second.errors, second.input_bytes_total),
+            (2, 1, 4)
+        );
+        assert_eq!(second.error_rate(), 0.5);
+        assert!(second.avg_latency_ms() >= 0.0);
+
+        collecting.reset();
+        assert_eq!(collecting.metrics()[0], ProcessorMetrics::default());
+        assert_eq!(ProcessorMetrics::default().error_rate(), 0.0);
+    }
+}
@@ -0,0 +1,60 @@
+//! RFC 3986 percent-encoding shared by the URI component helpers.
+
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~')
+}
+
+fn hex_val(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|v| v as u8)
+}
+
+/// Percent-encode every byte outside the unreserved set; with `plus_space`,
+/// spaces become `+` as in `application/x-www-form-urlencoded`.
+pub(crate) fn encode(data: &[u8], plus_space: bool) -> String {
+    let mut out = String::with_capacity(data.len());
+    for &b in data {
+        if is_unreserved(b) {
+            out.push(b as char);
+        } else if plus_space && b == b' ' {
+            out.push('+');
+        } else {
+            out.push('%');
+            out.push(HEX[usize::from(b >> 4)] as char);
+            out.push(HEX[usize::from(b & 0xf)] as char);
+        }
+    }
+    out
+}
+
+/// Inverse of [`encode`]; `%` must be followed by two hex digits.
+pub(crate) fn decode(text: &str, plus_space: bool) -> Result<Vec<u8>, String> {
+    let src = text.as_bytes();
+    let mut out = Vec::with_capacity(src.len());
+    let mut i = 0;
+    while i < src.len() {
+        match src[i] {
+            b'%' => {
+                let digits = match src.get(i + 1..i + 3) {
+                    Some(&[hi, lo]) => hex_val(hi).zip(hex_val(lo)),
+                    _ => None,
+                };
+                let Some((hi, lo)) = digits else {
+                    return Err(format!("invalid escape at byte {}", i));
+                };
+                out.push(hi << 4 | lo);
+                i += 3;
+            }
+            b'+' if plus_space => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    Ok(out)
+}
@@ -0,0 +1,1239 @@
+//! Ordered chains of [`PipeProcessor`](crate::PipeProcessor)s.
+
+use std::ops::{Add, AddAssign};
+use std::sync::Arc;
+use std::time::Instant;
+
+use orion_error::conversion::ToStructError;
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::processors::{InspectProcessor, RetryPolicy, RetryingProcessor};
+use crate::{
+    IntoRawData, ParseContext, PipeHold, PipelineStats, RawDataKind, StepStat, WparseReason,
+    WparseResult,
+};
+
+/// Diagnostics hooks invoked by [`Pipeline::run`] around every step.
+///
+/// Lets callers route stage events to any logging or metrics backend without
+/// this crate depending on one.
+pub trait PipeObserver: Send + Sync {
+    fn on_stage_start(&self, name: &str, input_len: usize);
+
+    fn on_stage_end(&self, name: &str, result: &WparseResult<RawData>);
+}
+
+/// A sequence of processors where each step's output feeds the next step.
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    name: Option<String>,
+    steps: Vec<PipeHold>,
+    observer: Option<Arc<dyn PipeObserver>>,
+    max_intermediate_bytes: Option<usize>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a pipeline from an already assembled sequence of processors.
+    pub fn from_processors(processors: impl IntoIterator<Item = PipeHold>) -> Self {
+        processors.into_iter().collect()
+    }
+
+    /// Name the pipeline, e.g. for lookup in a [`PipelineRegistry`].
+    ///
+    /// [`PipelineRegistry`]: crate::PipelineRegistry
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Append a processor, builder style.
+    pub fn with(mut self, processor: PipeHold) -> Self {
+        self.steps.push(processor);
+        self
+    }
+
+    pub fn push(&mut self, processor: PipeHold) {
+        self.steps.push(processor);
+    }
+
+    /// Fail a run with `FormatError` as soon as any step outputs more than
+    /// `limit` bytes, guarding chained decoders against amplification.
+    /// Pipelines are unbounded by default.
+    pub fn with_max_intermediate_bytes(mut self, limit: usize) -> Self {
+        self.max_intermediate_bytes = Some(limit);
+        self
+    }
+
+    /// Attach an observer notified before and after each step of [`run`](Self::run).
+    pub fn with_observer(mut self, observer: Arc<dyn PipeObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Wrap every current step in a [`RetryingProcessor`] using `policy`.
+    ///
+    /// Non-retriable errors are still returned immediately without retrying.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.steps = self
+            .steps
+            .into_iter()
+            .map(|step| Arc::new(RetryingProcessor::new(step, policy)) as PipeHold)
+            .collect();
+        self
+    }
+
+    /// Remove consecutive steps with equal [`identity_key`]s, keeping the
+    /// first of each run. Steps without a key are always kept.
+    ///
+    /// [Passthrough](crate::PipeProcessor::is_passthrough) steps such as
+    /// inspection taps do not separate a run; those following a removed
+    /// step are removed with it.
+    ///
+    /// [`identity_key`]: crate::PipeProcessor::identity_key
+    pub fn dedup_adjacent(&mut self) {
+        let mut prev_key = None;
+        let mut removing = false;
+        self.steps.retain(|step| {
+            if step.is_passthrough() {
+                return !removing;
+            }
+            let key = step.identity_key();
+            removing = key.is_some() && key == prev_key;
+            prev_key = key;
+            !removing
+        });
+    }
+
+    /// Tap the output of every current step with `f(step_index, step_name, &data)`.
+    ///
+    /// An [`InspectProcessor`] is inserted after each step, so [`len`](Self::len)
+    /// grows accordingly; the data flowing through is not changed.
+    pub fn inspect<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, &str, &RawData) + Send + Sync + 'static,
+    {
+        let f = Arc::new(f);
+        let steps = std::mem::take(&mut self.steps);
+        for (index, step) in steps.into_iter().enumerate() {
+            let tap = InspectProcessor::new(index, step.name(), f.clone())
+                .with_type_hint(step.output_type_hint());
+            self.steps.push(step);
+            self.steps.push(Arc::new(tap));
+        }
+        self
+    }
+
+    /// Tap only the output of the step at `index`.
+    ///
+    /// # Panics
+    /// If `index >= len()`.
+    pub fn inspect_at<F>(mut self, index: usize, f: F) -> Self
+    where
+        F: Fn(usize, &str, &RawData) + Send + Sync + 'static,
+    {
+        let step = &self.steps[index];
+        let tap = InspectProcessor::new(index, step.name(), Arc::new(f))
+            .with_type_hint(step.output_type_hint());
+        self.steps.insert(index + 1, Arc::new(tap));
+        self
+    }
+
+    /// Split into the steps `[0..index]` and `[index..]`; both halves keep
+    /// the name and observer.
+    ///
+    /// # Panics
+    /// If `index > len()`.
+    pub fn split_at(mut self, index: usize) -> (Pipeline, Pipeline) {
+        let tail = self.steps.split_off(index);
+        let rest = Pipeline {
+            name: self.name.clone(),
+            steps: tail,
+            observer: self.observer.clone(),
+            max_intermediate_bytes: self.max_intermediate_bytes,
+        };
+        (self, rest)
+    }
+
+    /// The first `n` steps (all of them if `n >= len()`).
+    pub fn take_first(self, n: usize) -> Pipeline {
+        let n = n.min(self.len());
+        self.split_at(n).0
+    }
+
+    /// The last `n` steps (all of them if `n >= len()`).
+    pub fn take_last(self, n: usize) -> Pipeline {
+        let at = self.len().saturating_sub(n);
+        self.split_at(at).1
+    }
+
+    /// The steps `[start..end]`.
+    ///
+    /// # Panics
+    /// If `start > end` or `end > len()`, like slice indexing.
+    pub fn slice(mut self, start: usize, end: usize) -> Pipeline {
+        self.steps = self.steps.drain(start..end).collect();
+        self
+    }
+
+    pub fn steps(&self) -> &[PipeHold] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Run `input` through every step in order, stopping at the first error.
+    pub fn run(&self, input: RawData) -> WparseResult<RawData> {
+        let mut steps = self.steps.iter().enumerate();
+        let Some(observer) = &self.observer else {
+            return steps.try_fold(input, |data, (index, step)| {
+                step.process(data)
+                    .and_then(|out| self.check_output(index, step, out))
+            });
+        };
+        steps.try_fold(input, |data, (index, step)| {
+            observer.on_stage_start(step.name(), data.len());
+            let result = step.process(data);
+            observer.on_stage_end(step.name(), &result);
+            result.and_then(|out| self.check_output(index, step, out))
+        })
+    }
+
+    /// Like [`run`](Self::run), but also timing each step.
+    ///
+    /// A lightweight alternative to [`MetricsCollectingPipeline`] for a
+    /// single run: stats are returned even when a step fails, including the
+    /// failing step's duration. The observer is not notified.
+    ///
+    /// [`MetricsCollectingPipeline`]: crate::MetricsCollectingPipeline
+    pub fn run_with_stats(&self, input: RawData) -> (WparseResult<RawData>, PipelineStats) {
+        let mut stats = PipelineStats {
+            steps: Vec::with_capacity(self.steps.len()),
+        };
+        let mut data = input;
+        for (index, step) in self.steps.iter().enumerate() {
+            let input_len = data.len();
+            let started = Instant::now();
+            let result = step.process(data);
+            let duration_ns = started.elapsed().as_nanos() as u64;
+            stats.steps.push(StepStat {
+                name: step.name().to_string(),
+                duration_ns,
+                input_len,
+                output_len: result.as_ref().map_or(0, |out| out.len()),
+            });
+            match result.and_then(|out| self.check_output(index, step, out)) {
+                Ok(out) => data = out,
+                Err(err) => return (Err(err), stats),
+            }
+        }
+        (Ok(data), stats)
+    }
+
+    /// Like [`run`](Self::run), but calling each step's
+    /// [`process_with`](crate::PipeProcessor::process_with) with the same
+    /// `ctx`, so steps can coordinate through it.
+    pub fn run_with(&self, input: RawData, ctx: &ParseContext) -> WparseResult<RawData> {
+        self.steps
+            .iter()
+            .enumerate()
+            .try_fold(input, |data, (index, step)| {
+                if let Some(observer) = &self.observer {
+                    observer.on_stage_start(step.name(), data.len());
+                }
+                let result = step.process_with(data, ctx);
+                if let Some(observer) = &self.observer {
+                    observer.on_stage_end(step.name(), &result);
+                }
+                result.and_then(|out| self.check_output(index, step, out))
+            })
+    }
+
+    /// Like [`run`](Self::run), but rewriting `data` through each step's
+    /// [`process_in_place`](crate::PipeProcessor::process_in_place).
+    ///
+    /// On error `data` may be left empty. The observer is not notified.
+    pub fn run_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+        for (index, step) in self.steps.iter().enumerate() {
+            step.process_in_place(data)?;
+            if let Some(limit) = self.max_intermediate_bytes
+                && data.len() > limit
+            {
+                let out = std::mem::replace(data, RawData::Bytes(bytes::Bytes::new()));
+                *data = self.check_output(index, step, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Like [`run`](Self::run), but fails with `EmptyData` on empty input
+    /// without calling any step.
+    pub fn run_nonempty(&self, input: RawData) -> WparseResult<RawData> {
+        if input.is_empty() {
+            return Err(DataErrKind::EmptyData.into());
+        }
+        self.run(input)
+    }
+
+    /// Like [`run`](Self::run), but returns `Ok(None)` for empty input
+    /// without calling any step.
+    pub fn run_if_nonempty(&self, input: RawData) -> WparseResult<Option<RawData>> {
+        if input.is_empty() {
+            return Ok(None);
+        }
+        self.run(input).map(Some)
+    }
+
+    /// Like [`run`](Self::run) but going through
+    /// [`process_split`](crate::PipeProcessor::process_split), returning the
+    /// final output plus every remainder tagged with the index of the step
+    /// that produced it. Remainders are not run through later steps.
+    ///
+    /// The observer is not notified.
+    pub fn run_split(&self, input: RawData) -> WparseResult<(RawData, Vec<(usize, RawData)>)> {
+        let mut remainders = Vec::new();
+        let mut data = input;
+        for (index, step) in self.steps.iter().enumerate() {
+            let (out, rest) = step.process_split(data)?;
+            remainders.extend(rest.map(|rest| (index, rest)));
+            data = self.check_output(index, step, out)?;
+        }
+        Ok((data, remainders))
+    }
+
+    /// Run every input through the pipeline independently, keeping input order.
+    pub fn run_batch(&self, inputs: Vec<RawData>) -> Vec<WparseResult<RawData>> {
+        inputs.into_iter().map(|input| self.run(input)).collect()
+    }
+
+    /// Parallel [`run_batch`](Self::run_batch) on the rayon global pool.
+    ///
+    /// Results keep input order; observers are called from worker threads.
+    #[cfg(feature = "rayon")]
+    pub fn run_batch_par(&self, inputs: Vec<RawData>) -> Vec<WparseResult<RawData>> {
+        use rayon::prelude::*;
+
+        inputs
+            .into_par_iter()
+            .map(|input| self.run(input))
+            .collect()
+    }
+
+    /// [`run`](Self::run) for any input convertible via [`IntoRawData`].
+    pub fn run_typed<T: IntoRawData>(&self, input: T) -> WparseResult<RawData> {
+        self.run(input.into_raw_data())
+    }
+
+    /// Enforce [`with_max_intermediate_bytes`](Self::with_max_intermediate_bytes)
+    /// on the output of step `index`.
+    pub(crate) fn check_output(
+        &self,
+        index: usize,
+        step: &PipeHold,
+        out: RawData,
+    ) -> WparseResult<RawData> {
+        match self.max_intermediate_bytes {
+            Some(limit) if out.len() > limit => Err(DataErrKind::FormatError(
+                format!(
+                    "{} produced {} bytes, over the {} byte limit",
+                    step_label(index, step),
+                    out.len(),
+                    limit
+                ),
+                None,
+            )
+            .into()),
+            _ => Ok(out),
+        }
+    }
+
+    /// Warnings for adjacent steps whose type hints disagree.
+    ///
+    /// Each entry is the index of the consuming step and a message naming both
+    /// steps. Hints are only compared when both sides provide one; `Bytes`
+    /// and `ArcBytes` count as the same binary kind. Mismatches still run, so
+    /// this is advisory. [Passthrough](crate::PipeProcessor::is_passthrough)
+    /// steps are skipped, so a tap does not hide a mismatch around it.
+    pub fn type_check(&self) -> Vec<(usize, String)> {
+        let compatible = |out: RawDataKind, input: RawDataKind| {
+            out == input || out.is_binary() && input.is_binary()
+        };
+        let steps: Vec<(usize, &PipeHold)> = self
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| !step.is_passthrough())
+            .collect();
+        steps
+            .windows(2)
+            .filter_map(|pair| {
+                let ((prev_index, prev), (index, step)) = (pair[0], pair[1]);
+                let out = prev.output_type_hint()?;
+                let input = step.input_type_hint()?;
+                (!compatible(out, input)).then(|| {
+                    (
+                        index,
+                        format!(
+                            "{} outputs {} but {} expects {}",
+                            step_label(prev_index, prev),
+                            out,
+                            step_label(index, step),
+                            input
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Pre-flight check: verify every step can run and that `input` makes it
+    /// through the whole pipeline.
+    ///
+    /// The input is cloned, so the caller keeps ownership. A step whose
+    /// `can_process()` is `false` yields `WparseReason::StepRejected`
+    /// (configuration error, code 400); a step whose `process` fails yields
+    /// `WparseReason::StepFailed` (runtime error, code 500) with the original
+    /// error attached as source. Both name the failing step.
+    pub fn dry_run(&self, input: &RawData) -> WparseResult<()> {
+        let mut data = input.clone();
+        for (index, step) in self.steps.iter().enumerate() {
+            check_step(index, step)?;
+            data = step.process(data).map_err(|err| {
+                WparseReason::StepFailed(step_label(index, step))
+                    .to_err()
+                    .with_source(err)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Like [`dry_run`](Self::dry_run) but only checks `can_process()`,
+    /// without running any data through the steps.
+    pub fn dry_run_no_data(&self) -> WparseResult<()> {
+        self.steps
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, step)| check_step(index, step))
+    }
+}
+
+/// Shows each step by processor name, since `PipeHold` itself is opaque.
+impl std::fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        struct StepNames<'a>(&'a [PipeHold]);
+
+        impl std::fmt::Debug for StepNames<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_list()
+                    .entries(self.0.iter().map(|step| step.name()))
+                    .finish()
+            }
+        }
+
+        let mut debug = f.debug_struct("Pipeline");
+        if let Some(name) = &self.name {
+            debug.field("name", name);
+        }
+        debug
+            .field("steps", &StepNames(&self.steps))
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl FromIterator<PipeHold> for Pipeline {
+    fn from_iter<I: IntoIterator<Item = PipeHold>>(iter: I) -> Self {
+        Self {
+            name: None,
+            steps: iter.into_iter().collect(),
+            observer: None,
+            max_intermediate_bytes: None,
+        }
+    }
+}
+
+impl From<Vec<PipeHold>> for Pipeline {
+    fn from(steps: Vec<PipeHold>) -> Self {
+        Self {
+            name: None,
+            steps,
+            observer: None,
+            max_intermediate_bytes: None,
+        }
+    }
+}
+
+/// `pipeline + processor` appends a step, like [`Pipeline::with`].
+///
+/// Two bare processors cannot be added (`PipeHold` and `Add` are both
+/// foreign to this crate); start from a pipeline, e.g.
+/// `Pipeline::new() + a + b`.
+impl Add<PipeHold> for Pipeline {
+    type Output = Pipeline;
+
+    fn add(self, processor: PipeHold) -> Pipeline {
+        self.with(processor)
+    }
+}
+
+/// `processor + pipeline` runs `processor` before the steps of `pipeline`,
+/// whose name, observer and limits are kept.
+impl Add<Pipeline> for PipeHold {
+    type Output = Pipeline;
+
+    fn add(self, pipeline: Pipeline) -> Pipeline {
+        Pipeline::from(vec![self]) + pipeline
+    }
+}
+
+/// `a + b` runs the steps of `a` followed by those of `b`. The name and
+/// observer of `a` are kept; `b`'s are used only when `a` has none. The
+/// stricter of the two intermediate size limits applies.
+impl Add for Pipeline {
+    type Output = Pipeline;
+
+    fn add(mut self, other: Pipeline) -> Pipeline {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign<PipeHold> for Pipeline {
+    fn add_assign(&mut self, processor: PipeHold) {
+        self.push(processor);
+    }
+}
+
+impl AddAssign for Pipeline {
+    fn add_assign(&mut self, other: Pipeline) {
+        self.steps.extend(other.steps);
+        if self.name.is_none() {
+            self.name = other.name;
+        }
+        if self.observer.is_none() {
+            self.observer = other.observer;
+        }
+        self.max_intermediate_bytes =
+            match (self.max_intermediate_bytes, other.max_intermediate_bytes) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+    }
+}
+
+fn step_label(index: usize, step: &PipeHold) -> String {
+    format!("step {} ({})", index, step.name())
+}
+
+fn check_step(index: usize, step: &PipeHold) -> WparseResult<()> {
+    if step.can_process() {
+        Ok(())
+    } else {
+        Err(WparseReason::StepRejected(step_label(index, step)).into_traced_err())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::Strip;
+    use crate::{DataRecordRawExt, PipeProcessor};
+    use orion_error::reason::ErrorCode;
+    use std::sync::Mutex;
+
+    struct Unconfigured;
+
+    impl PipeProcessor for Unconfigured {
+        fn process(&self, data: RawData) -> WparseResult<RawData> {
+            Ok(data)
+        }
+
+        fn name(&self) -> &'static str {
+            "unconfigured"
+        }
+
+        fn can_process(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_run_chains_steps() {
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(0, 1)));
+        assert_eq!(pipeline.len(), 2);
+
+        let out = pipeline.run(RawData::from_string("[x]")).unwrap();
+        assert_eq!(out.as_bytes(), b"x");
+    }
+
+    #[test]
+    fn test_run_nonempty_skips_steps_on_empty_input() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = calls.clone();
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(0, 0)))
+            .inspect(move |_, _, _| {
+                seen.fetch_add(1, Ordering::SeqCst);
+            });
+
+        let err = pipeline.run_nonempty(RawData::from_string("")).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::data_error());
+        assert_eq!(err.detail().as_deref(), Some("empty data"));
+        assert!(
+            pipeline
+                .run_if_nonempty(RawData::from_string(""))
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let out = pipeline.run_if_nonempty(RawData::from_string("x")).unwrap();
+        assert_eq!(out.unwrap().as_bytes(), b"x");
+        assert_eq!(
+            pipeline
+                .run_nonempty(RawData::from_string("y"))
+                .unwrap()
+                .as_bytes(),
+            b"y"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(!Strip::new(1, 0).accepts_empty());
+    }
+
+    #[test]
+    fn test_run_batch_keeps_order_and_errors() {
+        let pipeline = Pipeline::new().with(Arc::new(Strip::new(1, 1)));
+        let inputs = || {
+            ["<a>", "x", "<bc>"]
+                .into_iter()
+                .map(RawData::from_string)
+                .collect::<Vec<_>>()
+        };
+
+        let check = |results: Vec<WparseResult<RawData>>| {
+            assert_eq!(results.len(), 3);
+            assert_eq!(results[0].as_ref().unwrap().as_bytes(), b"a");
+            assert!(results[1].is_err());
+            assert_eq!(results[2].as_ref().unwrap().as_bytes(), b"bc");
+        };
+        check(pipeline.run_batch(inputs()));
+        #[cfg(feature = "rayon")]
+        check(pipeline.run_batch_par(inputs()));
+    }
+
+    #[test]
+    fn test_inspect_taps_every_step() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = seen.clone();
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(0, 1)))
+            .inspect(move |index, name, data| {
+                log.lock()
+                    .unwrap()
+                    .push((index, name.to_string(), data.as_bytes().to_vec()));
+            });
+        assert_eq!(pipeline.len(), 4);
+
+        let out = pipeline.run(RawData::from_string("[ab]")).unwrap();
+        assert_eq!(out.as_bytes(), b"ab");
+        assert_eq!(
+            *seen.lock().unwrap(),
+            [
+                (0, "strip".to_string(), b"ab]".to_vec()),
+                (1, "strip".to_string(), b"ab".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspect_at_single_step() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = calls.clone();
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(0, 1)))
+            .inspect_at(1, move |index, _, data| {
+                log.lock().unwrap().push((index, data.len()));
+            });
+
+        assert_eq!(
+            pipeline
+                .run(RawData::from_string("[ab]"))
+                .unwrap()
+                .as_bytes(),
+            b"ab"
+        );
+        assert_eq!(
+            pipeline
+                .run(RawData::from_string("<c>"))
+                .unwrap()
+                .as_bytes(),
+            b"c"
+        );
+        assert_eq!(*calls.lock().unwrap(), [(1, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_type_check_flags_string_into_binary() {
+        use crate::processors::{Base32Decode, Base32Encode, ByteOrder, LengthPrefixStrip};
+
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Base32Encode::default()))
+            .with(Arc::new(LengthPrefixStrip::new(2, ByteOrder::Big)));
+        assert_eq!(
+            pipeline.type_check(),
+            [(
+                1,
+                "step 0 (base32_encode) outputs String but step 1 (length_prefix_strip) expects Bytes"
+                    .to_string()
+            )]
+        );
+
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Base32Encode::default()))
+            .with(Arc::new(Base32Decode::default()))
+            .with(Arc::new(LengthPrefixStrip::new(2, ByteOrder::Big)))
+            .with(Arc::new(Strip::new(1, 0)));
+        assert!(pipeline.type_check().is_empty());
+    }
+
+    #[test]
+    fn test_type_check_sees_through_decorators() {
+        use crate::processors::{
+            Base32Encode, ByteOrder, LengthPrefixStrip, RetryBackoff, TimeGuard, TimedProcessor,
+        };
+        use std::time::Duration;
+
+        let expected = [(
+            1,
+            "step 0 (base32_encode) outputs String but step 1 (length_prefix_strip) expects Bytes"
+                .to_string(),
+        )];
+        let retried = Pipeline::new()
+            .with(Arc::new(Base32Encode::default()))
+            .with(Arc::new(LengthPrefixStrip::new(2, ByteOrder::Big)))
+            .with_retry(RetryPolicy::new(2, RetryBackoff::Fixed(Duration::ZERO)));
+        assert_eq!(retried.type_check(), expected);
+
+        let second = Duration::from_secs(1);
+        let wrapped = Pipeline::new()
+            .with(Arc::new(TimedProcessor::new(
+                Arc::new(Base32Encode::default()),
+                second,
+            )))
+            .with(Arc::new(TimeGuard::new(
+                Arc::new(LengthPrefixStrip::new(2, ByteOrder::Big)),
+                second,
+            )));
+        assert_eq!(wrapped.type_check(), expected);
+    }
+
+    #[test]
+    fn test_type_check_looks_through_inspect_taps() {
+        use crate::processors::{Base32Encode, ByteOrder, LengthPrefixStrip};
+
+        let tapped = Pipeline::new()
+            .with(Arc::new(Base32Encode::default()))
+            .with(Arc::new(LengthPrefixStrip::new(2, ByteOrder::Big)))
+            .inspect(|_, _, _| {});
+        assert_eq!(tapped.len(), 4);
+        assert_eq!(
+            tapped.type_check(),
+            [(
+                2,
+                "step 0 (base32_encode) outputs String but step 2 (length_prefix_strip) expects Bytes"
+                    .to_string(),
+            )]
+        );
+
+        let tap = &tapped.steps()[1];
+        assert_eq!(tap.input_type_hint(), Some(RawDataKind::String));
+        assert_eq!(tap.output_type_hint(), Some(RawDataKind::String));
+        assert!(tap.is_pure() && tap.is_idempotent() && tap.is_passthrough());
+    }
+
+    #[test]
+    fn test_run_split_collects_remainders() {
+        use crate::processors::Cap;
+
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Cap::new(6)))
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Cap::new(3)));
+        let (out, remainders) = pipeline
+            .run_split(RawData::from_string("#abcdefgh"))
+            .unwrap();
+        assert_eq!(out.as_bytes(), b"abc");
+        let remainders: Vec<_> = remainders
+            .iter()
+            .map(|(index, rest)| (*index, rest.as_bytes()))
+            .collect();
+        assert_eq!(remainders, [(0, &b"fgh"[..]), (2, b"de")]);
+
+        use crate::processors::RetryBackoff;
+        let retrying =
+            pipeline.with_retry(RetryPolicy::new(2, RetryBackoff::Fixed(Default::default())));
+        let (_, remainders) = retrying
+            .run_split(RawData::from_string("#abcdefgh"))
+            .unwrap();
+        assert_eq!(remainders.len(), 2);
+    }
+
+    struct Frame {
+        tag: u8,
+        body: &'static str,
+    }
+
+    impl IntoRawData for Frame {
+        fn into_raw_data(self) -> RawData {
+            let mut bytes = vec![self.tag];
+            bytes.extend_from_slice(self.body.as_bytes());
+            bytes.into_raw_data()
+        }
+    }
+
+    #[test]
+    fn test_run_typed_accepts_custom_type() {
+        let pipeline = Pipeline::new().with(Arc::new(Strip::new(1, 0)));
+        let frame = Frame {
+            tag: 0x01,
+            body: "payload",
+        };
+        assert_eq!(pipeline.run_typed(frame).unwrap().as_bytes(), b"payload");
+        assert_eq!(pipeline.run_typed("#x").unwrap().as_bytes(), b"x");
+    }
+
+    #[test]
+    fn test_construct_from_processor_list() {
+        let strip_head: PipeHold = Arc::new(Strip::new(1, 0));
+        let strip_tail: PipeHold = Arc::new(Strip::new(0, 2));
+        let steps = vec![strip_head, strip_tail];
+
+        let collected: Pipeline = steps.clone().into_iter().collect();
+        let from_vec = Pipeline::from(steps.clone());
+        let from_iter = Pipeline::from_processors(steps);
+        for pipeline in [collected, from_vec, from_iter] {
+            assert_eq!(pipeline.len(), 2);
+            // Head strip first, then tail strip: "<abc>!" -> "abc>!" -> "abc".
+            let out = pipeline.run(RawData::from_string("<abc>!")).unwrap();
+            assert_eq!(out.as_bytes(), b"abc");
+        }
+    }
+
+    #[test]
+    fn test_add_composes_pipelines() {
+        let strip_head: PipeHold = Arc::new(Strip::new(1, 0));
+        let strip_tail: PipeHold = Arc::new(Strip::new(0, 2));
+
+        let mut pipeline = Pipeline::new() + strip_head.clone();
+        pipeline += Pipeline::from(vec![strip_tail.clone()]);
+        assert_eq!(pipeline.len(), 2);
+        let out = pipeline.run(RawData::from_string("<abc>!")).unwrap();
+        assert_eq!(out.as_bytes(), b"abc");
+
+        let combined = pipeline.clone() + Pipeline::new().with(strip_head) + strip_tail;
+        assert_eq!(combined.len(), 4);
+        let out = combined.run(RawData::from_string("<<abc>!>!")).unwrap();
+        assert_eq!(out.as_bytes(), b"abc");
+    }
+
+    #[test]
+    fn test_split_at_yields_independent_halves() {
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(0, 1)))
+            .with(Arc::new(Strip::new(2, 0)))
+            .with(Arc::new(Strip::new(0, 2)));
+
+        let (head, tail) = pipeline.clone().split_at(2);
+        assert_eq!((head.len(), tail.len()), (2, 2));
+        assert_eq!(
+            head.run(RawData::from_string("[x]")).unwrap().as_bytes(),
+            b"x"
+        );
+        assert_eq!(
+            tail.run(RawData::from_string("<<x>>")).unwrap().as_bytes(),
+            b"x"
+        );
+
+        assert_eq!(pipeline.clone().take_first(3).len(), 3);
+        assert_eq!(pipeline.clone().take_first(9).len(), 4);
+        assert_eq!(pipeline.clone().take_last(1).len(), 1);
+        let middle = pipeline.clone().slice(1, 3);
+        assert_eq!(
+            middle.run(RawData::from_string("abx]")).unwrap().as_bytes(),
+            b"x"
+        );
+        assert!(pipeline.split_at(4).1.is_empty());
+    }
+
+    #[test]
+    fn test_max_intermediate_bytes_aborts_expansion() {
+        use crate::processors::Wrap;
+
+        let grow: PipeHold = Arc::new(Wrap::new(b"<<".to_vec(), b">>".to_vec()));
+        let pipeline = Pipeline::from(vec![grow.clone(), grow.clone(), grow]);
+        assert_eq!(pipeline.run(RawData::from_string("x")).unwrap().len(), 13);
+
+        let bounded = pipeline.with_max_intermediate_bytes(8);
+        let err = bounded.run(RawData::from_string("x")).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::data_error());
+        let detail = err.detail().clone().unwrap();
+        assert!(
+            detail.contains("step 1 (wrap) produced 9 bytes"),
+            "{detail}"
+        );
+        assert!(bounded.run_split(RawData::from_string("x")).is_err());
+        assert_eq!(
+            bounded
+                .clone()
+                .take_first(1)
+                .run(RawData::from_string("x"))
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_run_with_threads_context() {
+        use wp_model_core::model::DataField;
+
+        /// Records the payload length and the tenant set by the caller.
+        struct Tag;
+
+        impl PipeProcessor for Tag {
+            fn process(&self, data: RawData) -> WparseResult<RawData> {
+                Ok(data)
+            }
+
+            fn name(&self) -> &'static str {
+                "tag"
+            }
+
+            fn process_with(&self, data: RawData, ctx: &ParseContext) -> WparseResult<RawData> {
+                let tenant = ctx.get::<String>().unwrap_or_default();
+                let mut record = ctx.record();
+                record.push_owned(DataField::from_chars("tenant", tenant));
+                record.push_owned(DataField::from_digit(
+                    "offset",
+                    (ctx.offset() + data.len()) as i64,
+                ));
+                Ok(data)
+            }
+        }
+
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 1)))
+            .with(Arc::new(Tag));
+        let ctx = ParseContext::new().with_offset(100);
+        ctx.insert("acme".to_string());
+
+        let out = pipeline
+            .run_with(RawData::from_string("[abc]"), &ctx)
+            .unwrap();
+        assert_eq!(out.as_bytes(), b"abc");
+        let record = ctx.into_record();
+        assert_eq!(record.get_raw("tenant").unwrap().as_bytes(), b"acme");
+        assert_eq!(record.get_raw("offset").unwrap().as_bytes(), b"103");
+    }
+
+    #[test]
+    fn test_run_in_place_uses_overrides() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// XOR mask counting how often it had to build a new payload.
+        struct Mask {
+            allocations: AtomicUsize,
+        }
+
+        impl PipeProcessor for Mask {
+            fn process(&self, data: RawData) -> WparseResult<RawData> {
+                self.allocations.fetch_add(1, Ordering::SeqCst);
+                let masked: Vec<u8> = data.as_bytes().iter().map(|b| b ^ 0x20).collect();
+                Ok(RawData::from_arc_bytes(Arc::new(masked)))
+            }
+
+            fn name(&self) -> &'static str {
+                "mask"
+            }
+
+            fn process_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+                if let RawData::ArcBytes(arc) = data
+                    && let Some(buf) = Arc::get_mut(arc)
+                {
+                    buf.iter_mut().for_each(|b| *b ^= 0x20);
+                    return Ok(());
+                }
+                let input = std::mem::replace(data, RawData::from_string(""));
+                *data = self.process(input)?;
+                Ok(())
+            }
+        }
+
+        let mask = Arc::new(Mask {
+            allocations: AtomicUsize::new(0),
+        });
+        let pipeline = Pipeline::new().with(mask.clone()).with(mask.clone());
+
+        let mut data = RawData::from_arc_bytes(Arc::new(b"abc".to_vec()));
+        let ptr = data.as_bytes().as_ptr();
+        pipeline.run_in_place(&mut data).unwrap();
+        assert_eq!(data.as_bytes(), b"abc");
+        assert_eq!(data.as_bytes().as_ptr(), ptr);
+        assert_eq!(mask.allocations.load(Ordering::SeqCst), 0);
+
+        // Steps without an override fall back to `process`.
+        let mut data = RawData::from_string("[x]");
+        let strip = Pipeline::new().with(Arc::new(Strip::new(1, 1)));
+        strip.run_in_place(&mut data).unwrap();
+        assert_eq!(data.as_bytes(), b"x");
+    }
+
+    #[test]
+    fn test_debug_lists_processor_names() {
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Unconfigured));
+        assert_eq!(
+            format!("{:?}", pipeline),
+            r#"Pipeline { steps: ["strip", "unconfigured"], observer: false }"#
+        );
+        assert!(format!("{:#?}", pipeline).contains("\"unconfigured\""));
+    }
+
+    #[test]
+    fn test_run_with_stats_records_failing_step() {
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 1)))
+            .with(Arc::new(Strip::new(0, 1)));
+
+        let (out, stats) = pipeline.run_with_stats(RawData::from_string("[ab]"));
+        assert_eq!(out.unwrap().as_bytes(), b"a");
+        assert_eq!(stats.steps.len(), pipeline.len());
+        assert_eq!(
+            stats
+                .steps
+                .iter()
+                .map(|s| (s.name.as_str(), s.input_len, s.output_len))
+                .collect::<Vec<_>>(),
+            [("strip", 4, 2), ("strip", 2, 1)]
+        );
+        assert_eq!(stats.total_input_bytes(), 6);
+        assert_eq!(
+            stats.total_duration_ns(),
+            stats.steps.iter().map(|s| s.duration_ns).sum::<u64>()
+        );
+
+        let failing = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 1)))
+            .with(Arc::new(Strip::new(5, 0)))
+            .with(Arc::new(Strip::new(0, 0)));
+        let (out, stats) = failing.run_with_stats(RawData::from_string("[ab]"));
+        assert!(out.is_err());
+        assert_eq!(stats.steps.len(), 2);
+        assert_eq!(
+            (stats.steps[1].input_len, stats.steps[1].output_len),
+            (2, 0)
+        );
+        assert!(stats.total_duration_ns() >= stats.steps[1].duration_ns);
+    }
+
+    #[test]
+    fn test_with_name_survives_composition() {
+        let named = Pipeline::new()
+            .with_name("syslog")
+            .with(Arc::new(Strip::new(1, 0)));
+        assert_eq!(named.name(), Some("syslog"));
+        assert_eq!(
+            format!("{:?}", named),
+            r#"Pipeline { name: "syslog", steps: ["strip"], observer: false }"#
+        );
+
+        let (head, tail) = (named + Pipeline::new().with_name("other")).split_at(0);
+        assert_eq!(head.name(), Some("syslog"));
+        assert_eq!(tail.name(), Some("syslog"));
+        assert_eq!((Pipeline::new() + tail).name(), Some("syslog"));
+    }
+
+    #[derive(Default)]
+    struct EventLog(Mutex<Vec<String>>);
+
+    impl PipeObserver for EventLog {
+        fn on_stage_start(&self, name: &str, input_len: usize) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("start {} {}", name, input_len));
+        }
+
+        fn on_stage_end(&self, name: &str, result: &WparseResult<RawData>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("end {} {}", name, result.is_ok()));
+        }
+    }
+
+    #[test]
+    fn test_observer_sees_each_stage() {
+        let log = Arc::new(EventLog::default());
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(5, 0)))
+            .with_observer(log.clone());
+
+        assert!(pipeline.run(RawData::from_string("abc")).is_err());
+        assert_eq!(
+            *log.0.lock().unwrap(),
+            [
+                "start strip 3",
+                "end strip true",
+                "start strip 2",
+                "end strip false"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_retry_keeps_steps() {
+        use crate::processors::RetryBackoff;
+        use std::time::Duration;
+
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 1)))
+            .with_retry(RetryPolicy::new(3, RetryBackoff::Fixed(Duration::ZERO)));
+        assert_eq!(pipeline.len(), 1);
+        assert_eq!(pipeline.steps()[0].name(), "strip");
+        assert_eq!(
+            pipeline
+                .run(RawData::from_string("<a>"))
+                .unwrap()
+                .as_bytes(),
+            b"a"
+        );
+    }
+
+    #[test]
+    fn test_dedup_adjacent_drops_equal_keys_only() {
+        let mut pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Strip::new(0, 1)))
+            .with(Arc::new(Unconfigured))
+            .with(Arc::new(Unconfigured))
+            .with(Arc::new(Strip::new(0, 1)));
+        pipeline.dedup_adjacent();
+
+        let keys: Vec<_> = pipeline.steps().iter().map(|s| s.identity_key()).collect();
+        assert_eq!(
+            keys,
+            [
+                Some("strip(1,0)".to_string()),
+                Some("strip(0,1)".to_string()),
+                None,
+                None,
+                Some("strip(0,1)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedup_adjacent_sees_through_decorators() {
+        use crate::processors::{RetryBackoff, TimeGuard, TimedProcessor, Wrap};
+        use std::time::Duration;
+
+        let wrap = || Arc::new(Wrap::new("<", ">")) as PipeHold;
+        let mut retried = Pipeline::new()
+            .with(wrap())
+            .with(wrap())
+            .with_retry(RetryPolicy::new(2, RetryBackoff::Fixed(Duration::ZERO)));
+        retried.dedup_adjacent();
+        assert_eq!(retried.len(), 1);
+        assert_eq!(
+            retried.steps()[0].identity_key().as_deref(),
+            Some("retry(RetryPolicy { max_attempts: 2, backoff: Fixed(0ns) },wrap(<,>))")
+        );
+
+        let second = Duration::from_secs(1);
+        let mut wrapped = Pipeline::new()
+            .with(Arc::new(TimedProcessor::new(wrap(), second)))
+            .with(Arc::new(TimedProcessor::new(wrap(), second)))
+            .with(Arc::new(TimeGuard::new(wrap(), second)))
+            .with(Arc::new(TimeGuard::new(wrap(), second)))
+            .with(Arc::new(TimeGuard::new(Arc::new(Unconfigured), second)))
+            .with(Arc::new(TimeGuard::new(Arc::new(Unconfigured), second)));
+        wrapped.dedup_adjacent();
+        assert_eq!(wrapped.len(), 4);
+        assert_eq!(
+            wrapped.steps()[1].identity_key().as_deref(),
+            Some("time_guard(1s,wrap(<,>))")
+        );
+    }
+
+    #[test]
+    fn test_dedup_adjacent_looks_through_inspect_taps() {
+        use crate::processors::Wrap;
+
+        let mut tapped = Pipeline::new()
+            .with(Arc::new(Wrap::new("<", ">")))
+            .with(Arc::new(Wrap::new("<", ">")))
+            .with(Arc::new(Strip::new(1, 1)))
+            .inspect(|_, _, _| {});
+        tapped.dedup_adjacent();
+        let names: Vec<&str> = tapped.steps().iter().map(|step| step.name()).collect();
+        assert_eq!(names, ["wrap", "inspect", "strip", "inspect"]);
+    }
+
+    #[test]
+    fn test_dry_run_rejected_step_is_config_error() {
+        let pipeline = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 0)))
+            .with(Arc::new(Unconfigured));
+
+        let err = pipeline.dry_run_no_data().unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &WparseReason::StepRejected("step 1 (unconfigured)".into())
+        );
+        assert_eq!(err.reason().error_code(), 400);
+        assert!(pipeline.dry_run(&RawData::from_string("xy")).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_failing_step_is_runtime_error() {
+        let pipeline = Pipeline::new().with(Arc::new(Strip::new(4, 0)));
+        assert!(pipeline.dry_run_no_data().is_ok());
+
+        let input = RawData::from_string("abc");
+        let err = pipeline.dry_run(&input).unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &WparseReason::StepFailed("step 0 (strip)".into())
+        );
+        assert_eq!(err.reason().error_code(), 500);
+        assert!(err.source_ref().is_some());
+        assert_eq!(input.as_bytes(), b"abc");
+
+        assert!(pipeline.dry_run(&RawData::from_string("abcdef")).is_ok());
+    }
+}
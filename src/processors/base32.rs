@@ -0,0 +1,244 @@
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::{PipeProcessor, RawDataKind, WparseResult};
+
+/// RFC 4648 base32 alphabet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Base32Alphabet {
+    /// `A-Z2-7` (RFC 4648 §6).
+    #[default]
+    Standard,
+    /// `0-9A-V`, which preserves sort order (RFC 4648 §7).
+    ExtendedHex,
+}
+
+impl Base32Alphabet {
+    fn symbols(self) -> &'static [u8; 32] {
+        match self {
+            Base32Alphabet::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            Base32Alphabet::ExtendedHex => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+        }
+    }
+
+    fn value(self, c: u8) -> Option<u8> {
+        let c = c.to_ascii_uppercase();
+        let v = match (self, c) {
+            (Base32Alphabet::Standard, b'A'..=b'Z') => c - b'A',
+            (Base32Alphabet::Standard, b'2'..=b'7') => c - b'2' + 26,
+            (Base32Alphabet::ExtendedHex, b'0'..=b'9') => c - b'0',
+            (Base32Alphabet::ExtendedHex, b'A'..=b'V') => c - b'A' + 10,
+            _ => return None,
+        };
+        Some(v)
+    }
+}
+
+/// Decodes base32 into `RawData::Bytes`.
+///
+/// Letters are accepted in either case. Padding is optional, but if present
+/// the input must be a whole number of 8-character groups. Characters outside
+/// the alphabet, misplaced `=`, or an impossible length fail with
+/// `FormatError`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Base32Decode {
+    pub alphabet: Base32Alphabet,
+}
+
+impl Base32Decode {
+    pub fn new(alphabet: Base32Alphabet) -> Self {
+        Self { alphabet }
+    }
+}
+
+/// Encodes into base32 as `RawData::String`, `=`-padded to a multiple of 8
+/// characters unless `padding` is `false`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base32Encode {
+    pub alphabet: Base32Alphabet,
+    pub padding: bool,
+}
+
+impl Base32Encode {
+    pub fn new(alphabet: Base32Alphabet, padding: bool) -> Self {
+        Self { alphabet, padding }
+    }
+}
+
+impl Default for Base32Encode {
+    fn default() -> Self {
+        Self::new(Base32Alphabet::Standard, true)
+    }
+}
+
+fn format_err(msg: String) -> crate::WparseError {
+    DataErrKind::FormatError(msg, None).into()
+}
+
+fn base32_encode(src: &[u8], alphabet: Base32Alphabet, padding: bool) -> String {
+    let symbols = alphabet.symbols();
+    let mut out = String::with_capacity(src.len().div_ceil(5) * 8);
+    let mut acc = 0u16;
+    let mut bits = 0;
+    for &b in src {
+        acc = (acc << 8) | u16::from(b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(symbols[usize::from((acc >> bits) & 0x1F)] as char);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(symbols[usize::from((acc << (5 - bits)) & 0x1F)] as char);
+    }
+    if padding {
+        while !out.len().is_multiple_of(8) {
+            out.push('=');
+        }
+    }
+    out
+}
+
+fn base32_decode(src: &[u8], alphabet: Base32Alphabet) -> WparseResult<Vec<u8>> {
+    let body_len = src.len() - src.iter().rev().take_while(|&&c| c == b'=').count();
+    let (body, padding) = src.split_at(body_len);
+    if !padding.is_empty() && !src.len().is_multiple_of(8) {
+        return Err(format_err(format!(
+            "base32_decode: padded length {} is not a multiple of 8",
+            src.len()
+        )));
+    }
+    // Valid final groups carry 2, 4, 5 or 7 symbols (1 to 4 bytes).
+    if matches!(body.len() % 8, 1 | 3 | 6) {
+        return Err(format_err(format!(
+            "base32_decode: invalid length {}",
+            body.len()
+        )));
+    }
+    let mut out = Vec::with_capacity(body.len() * 5 / 8);
+    let mut acc = 0u16;
+    let mut bits = 0;
+    for (i, &c) in body.iter().enumerate() {
+        let Some(v) = alphabet.value(c) else {
+            return Err(format_err(format!(
+                "base32_decode: invalid character at offset {}",
+                i
+            )));
+        };
+        acc = (acc << 5) | u16::from(v);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    Ok(out)
+}
+
+impl PipeProcessor for Base32Decode {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        base32_decode(data.as_bytes(), self.alphabet).map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    fn name(&self) -> &'static str {
+        "base32_decode"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::String)
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+}
+
+impl PipeProcessor for Base32Encode {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        Ok(RawData::String(base32_encode(
+            data.as_bytes(),
+            self.alphabet,
+            self.padding,
+        )))
+    }
+
+    fn name(&self) -> &'static str {
+        "base32_encode"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4648 §10 test vectors.
+    const VECTORS: [(&str, &str, &str); 7] = [
+        ("", "", ""),
+        ("f", "MY======", "CO======"),
+        ("fo", "MZXQ====", "CPNG===="),
+        ("foo", "MZXW6===", "CPNMU==="),
+        ("foob", "MZXW6YQ=", "CPNMUOG="),
+        ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+        ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ];
+
+    fn run(proc: &dyn PipeProcessor, input: &str) -> WparseResult<RawData> {
+        proc.process(RawData::from_string(input))
+    }
+
+    #[test]
+    fn test_base32_rfc_vectors() {
+        for (plain, std, hex) in VECTORS {
+            for (alphabet, encoded) in [
+                (Base32Alphabet::Standard, std),
+                (Base32Alphabet::ExtendedHex, hex),
+            ] {
+                let out = run(&Base32Encode::new(alphabet, true), plain).unwrap();
+                assert!(matches!(&out, RawData::String(s) if s == encoded));
+
+                let unpadded = run(&Base32Encode::new(alphabet, false), plain).unwrap();
+                assert_eq!(
+                    unpadded.as_bytes(),
+                    encoded.trim_end_matches('=').as_bytes()
+                );
+
+                let decoder = Base32Decode::new(alphabet);
+                for input in [encoded, encoded.trim_end_matches('=')] {
+                    let out = run(&decoder, input).unwrap();
+                    assert!(matches!(&out, RawData::Bytes(b) if &b[..] == plain.as_bytes()));
+                }
+            }
+        }
+        assert_eq!(
+            run(&Base32Decode::default(), "mzxw6ytb")
+                .unwrap()
+                .as_bytes(),
+            b"fooba"
+        );
+    }
+
+    #[test]
+    fn test_base32_decode_malformed() {
+        let decoder = Base32Decode::default();
+        for input in ["MZXW1===", "MZX", "MZXW6==", "MY==MY==", "M======="] {
+            let err = run(&decoder, input).unwrap_err();
+            assert!(err.detail().as_deref().unwrap().starts_with("format error"));
+        }
+        assert!(run(&Base32Decode::new(Base32Alphabet::ExtendedHex), "MZXW6===").is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use wp_model_core::raw::RawData;
+
+use crate::raw_ext::shared_slice;
+use crate::{PipeProcessor, WparseResult};
+
+/// Emits at most `max_len` bytes.
+///
+/// [`process_split`](PipeProcessor::process_split) returns the overflow as a
+/// remainder record; plain `process` drops it. Slices are zero-copy for
+/// `Bytes`/`ArcBytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cap {
+    pub max_len: usize,
+}
+
+impl Cap {
+    pub fn new(max_len: usize) -> Self {
+        Self { max_len }
+    }
+}
+
+impl PipeProcessor for Cap {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        self.process_split(data).map(|(head, _)| head)
+    }
+
+    fn name(&self) -> &'static str {
+        "cap"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    fn process_split(&self, data: RawData) -> WparseResult<(RawData, Option<RawData>)> {
+        if data.len() <= self.max_len {
+            return Ok((data, None));
+        }
+        let head = shared_slice(&data, 0..self.max_len);
+        let rest = shared_slice(&data, self.max_len..data.len());
+        Ok((head, Some(rest)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cap_splits_overflow() {
+        let cap = Cap::new(4);
+        let (head, rest) = cap
+            .process_split(RawData::from_string("page1rest"))
+            .unwrap();
+        assert_eq!(head.as_bytes(), b"page");
+        assert_eq!(rest.unwrap().as_bytes(), b"1rest");
+
+        let (head, rest) = cap.process_split(RawData::from_string("abc")).unwrap();
+        assert_eq!(head.as_bytes(), b"abc");
+        assert!(rest.is_none());
+
+        assert_eq!(
+            cap.process(RawData::from_string("abcdef"))
+                .unwrap()
+                .as_bytes(),
+            b"abcd"
+        );
+    }
+}
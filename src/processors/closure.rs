@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use wp_model_core::raw::RawData;
+
+use crate::{PipeHold, PipeProcessor, WparseReason, WparseResult};
+
+struct MapProcessor<F> {
+    name: &'static str,
+    f: F,
+}
+
+impl<F> PipeProcessor for MapProcessor<F>
+where
+    F: Fn(RawData) -> RawData + Send + Sync,
+{
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        Ok((self.f)(data))
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+struct FilterProcessor<P> {
+    name: &'static str,
+    predicate: P,
+}
+
+impl<P> PipeProcessor for FilterProcessor<P>
+where
+    P: Fn(&RawData) -> bool + Send + Sync,
+{
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        if (self.predicate)(&data) {
+            Ok(data)
+        } else {
+            Err(WparseReason::not_match().into_traced_err())
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// Wrap an infallible transformation into a processor named `name`.
+pub fn pipe_map(
+    name: &'static str,
+    f: impl Fn(RawData) -> RawData + Send + Sync + 'static,
+) -> PipeHold {
+    Arc::new(MapProcessor { name, f })
+}
+
+/// Pass data through unchanged when `predicate` holds, otherwise fail with
+/// `WparseReason::NotMatch`.
+pub fn pipe_filter(
+    name: &'static str,
+    predicate: impl Fn(&RawData) -> bool + Send + Sync + 'static,
+) -> PipeHold {
+    Arc::new(FilterProcessor { name, predicate })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawDataExt;
+
+    #[test]
+    fn test_pipe_map_uppercase() {
+        let upper = pipe_map("upper", |data| data.ascii_uppercase());
+        assert_eq!(upper.name(), "upper");
+        let out = upper.process(RawData::from_string("abc-1")).unwrap();
+        assert_eq!(out.as_bytes(), b"ABC-1");
+    }
+
+    #[test]
+    fn test_pipe_filter_min_len() {
+        let min_len = pipe_filter("min_len", |data| data.len() >= 3);
+        assert_eq!(min_len.name(), "min_len");
+        assert_eq!(
+            min_len
+                .process(RawData::from_string("abc"))
+                .unwrap()
+                .as_bytes(),
+            b"abc"
+        );
+
+        let err = min_len.process(RawData::from_string("ab")).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::not_match());
+    }
+}
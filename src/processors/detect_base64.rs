@@ -0,0 +1,147 @@
+use std::ops::Range;
+
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::{PipeProcessor, WparseReason, WparseResult};
+
+/// What [`DetectBase64`] returns once it has decoded a region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Base64Region {
+    /// Only the decoded bytes, as `RawData::Bytes`.
+    #[default]
+    Extract,
+    /// The whole input with the region replaced by its decoded bytes;
+    /// `RawData::String` when the result is valid UTF-8.
+    Replace,
+}
+
+/// Finds the longest base64-looking run in the input and decodes it.
+///
+/// A run is a maximal sequence of standard-alphabet characters followed by
+/// at most two `=`. To keep false positives down it must be at least
+/// `min_len` characters long, a whole number of 4-character groups, and
+/// contain at least one digit, `+`, `/` or `=` so plain words are skipped.
+/// Input without such a run fails with `NotMatch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectBase64 {
+    pub min_len: usize,
+    pub output: Base64Region,
+}
+
+impl DetectBase64 {
+    pub fn new(min_len: usize, output: Base64Region) -> Self {
+        Self { min_len, output }
+    }
+}
+
+impl Default for DetectBase64 {
+    fn default() -> Self {
+        Self::new(16, Base64Region::Extract)
+    }
+}
+
+fn is_base64_symbol(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'+' || b == b'/'
+}
+
+/// Longest run satisfying the heuristic documented on [`DetectBase64`].
+fn find_region(bytes: &[u8], min_len: usize) -> Option<Range<usize>> {
+    let mut best: Option<Range<usize>> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !is_base64_symbol(bytes[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < bytes.len() && is_base64_symbol(bytes[i]) {
+            i += 1;
+        }
+        let mut end = i;
+        while end < bytes.len() && end - i < 2 && bytes[end] == b'=' {
+            end += 1;
+        }
+        let run = &bytes[start..end];
+        let plausible = run.len() >= min_len.max(4)
+            && run.len().is_multiple_of(4)
+            && run
+                .iter()
+                .any(|&b| b.is_ascii_digit() || matches!(b, b'+' | b'/' | b'='));
+        if plausible && best.as_ref().is_none_or(|r| r.len() < run.len()) {
+            best = Some(start..end);
+        }
+        i = end;
+    }
+    best
+}
+
+impl PipeProcessor for DetectBase64 {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let bytes = data.as_bytes();
+        let decoded = find_region(bytes, self.min_len).and_then(|range| {
+            crate::base64::decode(&bytes[range.clone()])
+                .ok()
+                .map(|decoded| (range, decoded))
+        });
+        let Some((range, decoded)) = decoded else {
+            return Err(WparseReason::not_match_because("no base64 region found").into_traced_err());
+        };
+        match self.output {
+            Base64Region::Extract => Ok(RawData::Bytes(Bytes::from(decoded))),
+            Base64Region::Replace => {
+                let mut out = Vec::with_capacity(bytes.len() - range.len() + decoded.len());
+                out.extend_from_slice(&bytes[..range.start]);
+                out.extend_from_slice(&decoded);
+                out.extend_from_slice(&bytes[range.end..]);
+                Ok(match String::from_utf8(out) {
+                    Ok(text) => RawData::String(text),
+                    Err(err) => RawData::Bytes(Bytes::from(err.into_bytes())),
+                })
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "detect_base64"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "user=admin;token=42" in base64.
+    const LOG: &str = "ts=1 msg=login payload=dXNlcj1hZG1pbjt0b2tlbj00Mg== end";
+
+    #[test]
+    fn test_extracts_longest_region() {
+        let out = DetectBase64::default()
+            .process(RawData::from_string(LOG))
+            .unwrap();
+        assert_eq!(out.as_bytes(), b"user=admin;token=42");
+        assert!(matches!(out, RawData::Bytes(_)));
+    }
+
+    #[test]
+    fn test_replaces_region_in_text() {
+        let out = DetectBase64::new(16, Base64Region::Replace)
+            .process(RawData::from_string(LOG))
+            .unwrap();
+        assert!(
+            matches!(&out, RawData::String(s) if s == "ts=1 msg=login payload=user=admin;token=42 end")
+        );
+    }
+
+    #[test]
+    fn test_plain_text_is_not_match() {
+        let err = DetectBase64::new(4, Base64Region::Extract)
+            .process(RawData::from_string("HelloWorldFooBar and more"))
+            .unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::NotMatch(Some(_))));
+    }
+}
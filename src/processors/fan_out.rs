@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use wp_model_core::raw::RawData;
+
+use crate::{PipeHold, PipeProcessor, WparseResult};
+
+type Combiner = Arc<dyn Fn(Vec<RawData>) -> WparseResult<RawData> + Send + Sync>;
+
+/// Runs several independent processors on the same input and merges their
+/// outputs with a combiner.
+///
+/// Each branch receives its own clone of the input (a refcount bump for
+/// `Bytes`/`ArcBytes`). Branch outputs are passed to the combiner in branch
+/// order; the first branch error aborts the step.
+#[derive(Clone)]
+pub struct FanOut {
+    branches: Vec<PipeHold>,
+    combiner: Combiner,
+    parallel: bool,
+}
+
+impl FanOut {
+    pub fn new<F>(branches: Vec<PipeHold>, combiner: F) -> Self
+    where
+        F: Fn(Vec<RawData>) -> WparseResult<RawData> + Send + Sync + 'static,
+    {
+        Self {
+            branches,
+            combiner: Arc::new(combiner),
+            parallel: false,
+        }
+    }
+
+    /// Run branches concurrently.
+    ///
+    /// With the `rayon` feature branches are scheduled on the rayon global
+    /// pool; otherwise each gets a scoped thread, whose start-up cost is paid
+    /// on every `process` call. Only worth it when branches do substantial
+    /// work.
+    pub fn parallel(mut self) -> Self {
+        self.parallel = true;
+        self
+    }
+
+    fn run_branches(&self, data: &RawData) -> WparseResult<Vec<RawData>> {
+        if !self.parallel || self.branches.len() < 2 {
+            return self
+                .branches
+                .iter()
+                .map(|branch| branch.process(data.clone()))
+                .collect();
+        }
+        self.run_parallel(data)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn run_parallel(&self, data: &RawData) -> WparseResult<Vec<RawData>> {
+        use rayon::prelude::*;
+
+        self.branches
+            .par_iter()
+            .map(|branch| branch.process(data.clone()))
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn run_parallel(&self, data: &RawData) -> WparseResult<Vec<RawData>> {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .branches
+                .iter()
+                .map(|branch| {
+                    let input = data.clone();
+                    scope.spawn(move || branch.process(input))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| match handle.join() {
+                    Ok(result) => result,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                })
+                .collect()
+        })
+    }
+}
+
+impl PipeProcessor for FanOut {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let outputs = self.run_branches(&data)?;
+        (self.combiner)(outputs)
+    }
+
+    fn name(&self) -> &'static str {
+        "fan_out"
+    }
+
+    fn can_process(&self) -> bool {
+        self.branches.iter().all(|branch| branch.can_process())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::Strip;
+    use bytes::Bytes;
+
+    fn joined(outputs: Vec<RawData>) -> WparseResult<RawData> {
+        let parts: Vec<&[u8]> = outputs.iter().map(RawData::as_bytes).collect();
+        Ok(RawData::Bytes(Bytes::from(parts.join(&b'|'))))
+    }
+
+    fn branches() -> Vec<PipeHold> {
+        vec![Arc::new(Strip::new(1, 0)), Arc::new(Strip::new(0, 1))]
+    }
+
+    #[test]
+    fn test_fan_out_combines_branches_in_order() {
+        let input = RawData::from_arc_bytes(Arc::new(b"abc".to_vec()));
+        for fan_out in [
+            FanOut::new(branches(), joined),
+            FanOut::new(branches(), joined).parallel(),
+        ] {
+            let out = fan_out.process(input.clone()).unwrap();
+            assert_eq!(out.as_bytes(), b"bc|ab");
+        }
+    }
+
+    #[test]
+    fn test_fan_out_propagates_branch_error() {
+        let fan_out = FanOut::new(branches(), joined).parallel();
+        assert!(fan_out.process(RawData::from_string("")).is_err());
+    }
+}
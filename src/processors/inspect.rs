@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use wp_model_core::raw::RawData;
+
+use crate::{PipeProcessor, RawDataKind, WparseResult};
+
+/// Pass-through tap that shows each payload to a callback.
+///
+/// Inserted by [`Pipeline::inspect`](crate::Pipeline::inspect) and
+/// [`Pipeline::inspect_at`](crate::Pipeline::inspect_at) after the step it
+/// observes; calls `f(step_index, step_name, &data)` with that step's output
+/// and returns the data unchanged. The callback is shared, not cloned, so a
+/// tap allocates nothing per run.
+///
+/// A tap is a [passthrough](PipeProcessor::is_passthrough) step: it is pure
+/// and idempotent as far as the data goes, and reports the observed step's
+/// output kind as both its input and output hint.
+pub struct InspectProcessor<F> {
+    step_index: usize,
+    step_name: &'static str,
+    f: Arc<F>,
+    type_hint: Option<RawDataKind>,
+}
+
+impl<F> InspectProcessor<F> {
+    pub fn new(step_index: usize, step_name: &'static str, f: Arc<F>) -> Self {
+        Self {
+            step_index,
+            step_name,
+            f,
+            type_hint: None,
+        }
+    }
+
+    /// Report `kind`, normally the observed step's
+    /// [`output_type_hint`](PipeProcessor::output_type_hint), as the tap's
+    /// input and output hint.
+    pub fn with_type_hint(mut self, kind: Option<RawDataKind>) -> Self {
+        self.type_hint = kind;
+        self
+    }
+}
+
+impl<F> PipeProcessor for InspectProcessor<F>
+where
+    F: Fn(usize, &str, &RawData) + Send + Sync,
+{
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        (self.f)(self.step_index, self.step_name, &data);
+        Ok(data)
+    }
+
+    fn name(&self) -> &'static str {
+        "inspect"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    fn is_passthrough(&self) -> bool {
+        true
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        self.type_hint
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        self.type_hint
+    }
+}
@@ -0,0 +1,237 @@
+use bytes::{BufMut, BytesMut};
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::raw_ext::shared_slice;
+use crate::{PipeProcessor, RawDataKind, WparseReason, WparseResult};
+
+/// Byte order of a length header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    Big,
+    Little,
+}
+
+fn valid_header_size(header_size: usize) -> bool {
+    (1..=8).contains(&header_size)
+}
+
+fn header_size_error(name: &str, header_size: usize) -> WparseReason {
+    WparseReason::Encoding(format!(
+        "{}: header size {} not in 1..=8",
+        name, header_size
+    ))
+}
+
+/// Removes a fixed-width length header and checks it against the payload.
+///
+/// The first `header_size` bytes (1 to 8) are read as an unsigned integer in
+/// `byte_order`. The rest of the input must be exactly that long: a shorter
+/// payload fails with `WparseReason::Truncated`, and trailing bytes beyond
+/// the declared length fail with `FormatError` rather than being silently
+/// passed on. The payload is sliced without copying for `Bytes`/`ArcBytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthPrefixStrip {
+    pub header_size: usize,
+    pub byte_order: ByteOrder,
+}
+
+impl LengthPrefixStrip {
+    pub fn new(header_size: usize, byte_order: ByteOrder) -> Self {
+        Self {
+            header_size,
+            byte_order,
+        }
+    }
+}
+
+impl PipeProcessor for LengthPrefixStrip {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        if !valid_header_size(self.header_size) {
+            return Err(header_size_error(self.name(), self.header_size).into_traced_err());
+        }
+        let bytes = data.as_bytes();
+        let Some(header) = bytes.get(..self.header_size) else {
+            return Err(WparseReason::Truncated(format!(
+                "length_prefix_strip: {}-byte header, got {} bytes",
+                self.header_size,
+                bytes.len()
+            ))
+            .into_traced_err());
+        };
+        let declared = read_uint(header, self.byte_order);
+        let actual = (bytes.len() - self.header_size) as u64;
+        if actual < declared {
+            return Err(WparseReason::Truncated(format!(
+                "length_prefix_strip: declared {} payload bytes, got {}",
+                declared, actual
+            ))
+            .into_traced_err());
+        }
+        if actual > declared {
+            return Err(DataErrKind::FormatError(
+                format!(
+                    "length_prefix_strip: {} trailing bytes after declared payload",
+                    actual - declared
+                ),
+                None,
+            )
+            .into());
+        }
+        Ok(shared_slice(&data, self.header_size..data.len()))
+    }
+
+    fn name(&self) -> &'static str {
+        "length_prefix_strip"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn accepts_empty(&self) -> bool {
+        false
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+
+    fn can_process(&self) -> bool {
+        valid_header_size(self.header_size)
+    }
+}
+
+/// Prepends a fixed-width length header; the inverse of [`LengthPrefixStrip`].
+///
+/// Payloads whose length does not fit in `header_size` bytes fail with
+/// `WparseReason::Encoding`. Output is `RawData::Bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthPrefixAdd {
+    pub header_size: usize,
+    pub byte_order: ByteOrder,
+}
+
+impl LengthPrefixAdd {
+    pub fn new(header_size: usize, byte_order: ByteOrder) -> Self {
+        Self {
+            header_size,
+            byte_order,
+        }
+    }
+}
+
+impl PipeProcessor for LengthPrefixAdd {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        if !valid_header_size(self.header_size) {
+            return Err(header_size_error(self.name(), self.header_size).into_traced_err());
+        }
+        let len = data.len() as u64;
+        let bits = self.header_size * 8;
+        if bits < 64 && len >> bits != 0 {
+            return Err(WparseReason::Encoding(format!(
+                "length_prefix_add: {} bytes do not fit a {}-byte header",
+                len, self.header_size
+            ))
+            .into_traced_err());
+        }
+        let mut buf = BytesMut::with_capacity(self.header_size + data.len());
+        match self.byte_order {
+            ByteOrder::Big => buf.put_uint(len, self.header_size),
+            ByteOrder::Little => buf.put_uint_le(len, self.header_size),
+        }
+        buf.extend_from_slice(data.as_bytes());
+        Ok(RawData::Bytes(buf.freeze()))
+    }
+
+    fn name(&self) -> &'static str {
+        "length_prefix_add"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+
+    fn can_process(&self) -> bool {
+        valid_header_size(self.header_size)
+    }
+}
+
+fn read_uint(header: &[u8], order: ByteOrder) -> u64 {
+    let fold = |acc: u64, &b: &u8| (acc << 8) | u64::from(b);
+    match order {
+        ByteOrder::Big => header.iter().fold(0, fold),
+        ByteOrder::Little => header.iter().rev().fold(0, fold),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_two_byte_big_endian_round_trip() {
+        let add = LengthPrefixAdd::new(2, ByteOrder::Big);
+        let framed = add.process(RawData::from_string("hello")).unwrap();
+        assert_eq!(framed.as_bytes(), b"\x00\x05hello");
+
+        let strip = LengthPrefixStrip::new(2, ByteOrder::Big);
+        assert_eq!(strip.process(framed).unwrap().as_bytes(), b"hello");
+    }
+
+    #[test]
+    fn test_four_byte_little_endian_is_zero_copy() {
+        let arc = Arc::new(b"\x03\x00\x00\x00abc".to_vec());
+        let strip = LengthPrefixStrip::new(4, ByteOrder::Little);
+        let out = strip.process(RawData::from_arc_bytes(arc.clone())).unwrap();
+        assert_eq!(out.as_bytes(), b"abc");
+        assert_eq!(out.as_bytes().as_ptr(), arc[4..].as_ptr());
+
+        let add = LengthPrefixAdd::new(4, ByteOrder::Little);
+        assert_eq!(add.process(out).unwrap().as_bytes(), b"\x03\x00\x00\x00abc");
+    }
+
+    #[test]
+    fn test_short_payload_is_truncated() {
+        let strip = LengthPrefixStrip::new(2, ByteOrder::Big);
+        let err = strip
+            .process(RawData::from_string("\x00\x05hi"))
+            .unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Truncated(msg) if msg.contains("declared 5")));
+
+        let err = strip.process(RawData::from_string("\x00")).unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Truncated(_)));
+    }
+
+    #[test]
+    fn test_extra_bytes_are_rejected() {
+        let strip = LengthPrefixStrip::new(2, ByteOrder::Big);
+        let err = strip
+            .process(RawData::from_string("\x00\x01ab"))
+            .unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::data_error());
+        assert!(
+            err.detail()
+                .as_deref()
+                .unwrap()
+                .contains("1 trailing bytes")
+        );
+    }
+
+    #[test]
+    fn test_header_size_limits() {
+        assert!(!LengthPrefixStrip::new(0, ByteOrder::Big).can_process());
+        assert!(!LengthPrefixAdd::new(9, ByteOrder::Big).can_process());
+
+        let add = LengthPrefixAdd::new(1, ByteOrder::Big);
+        let err = add
+            .process(RawData::from_string("x".repeat(256)))
+            .unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Encoding(_)));
+    }
+}
@@ -0,0 +1,41 @@
+//! Built-in [`PipeProcessor`](crate::PipeProcessor) implementations.
+
+mod base32;
+mod cap;
+mod closure;
+mod detect_base64;
+mod fan_out;
+mod inspect;
+mod length_prefix;
+mod newline;
+mod quoted_printable;
+mod retry;
+mod search_replace;
+mod strip;
+mod time_guard;
+mod timed;
+mod varint;
+mod whitespace;
+mod wrap;
+#[cfg(feature = "compression")]
+mod zlib;
+
+pub use base32::{Base32Alphabet, Base32Decode, Base32Encode};
+pub use cap::Cap;
+pub use closure::{pipe_filter, pipe_map};
+pub use detect_base64::{Base64Region, DetectBase64};
+pub use fan_out::FanOut;
+pub use inspect::InspectProcessor;
+pub use length_prefix::{ByteOrder, LengthPrefixAdd, LengthPrefixStrip};
+pub use newline::{NewlineStyle, NormalizeNewlines};
+pub use quoted_printable::{QuotedPrintableDecode, QuotedPrintableEncode};
+pub use retry::{RetryBackoff, RetryPolicy, RetryingProcessor};
+pub use search_replace::SearchReplace;
+pub use strip::Strip;
+pub use time_guard::TimeGuard;
+pub use timed::{TimedProcessor, TimingStats};
+pub use varint::{VarintPrefixStrip, read_varint};
+pub use whitespace::CollapseWhitespace;
+pub use wrap::Wrap;
+#[cfg(feature = "compression")]
+pub use zlib::{ZlibDecode, ZlibEncode};
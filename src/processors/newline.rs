@@ -0,0 +1,122 @@
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::raw_ext::as_text;
+use crate::{PipeProcessor, WparseResult};
+
+/// Line ending written by [`NormalizeNewlines`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NewlineStyle {
+    #[default]
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl NewlineStyle {
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            NewlineStyle::Lf => b"\n",
+            NewlineStyle::CrLf => b"\r\n",
+            NewlineStyle::Cr => b"\r",
+        }
+    }
+}
+
+/// Rewrites every line ending (`\r\n`, bare `\n`, bare `\r`) to `target`.
+///
+/// `\r\n` counts as a single ending, so mixed input is not doubled. Valid
+/// UTF-8 input comes back as `RawData::String`, anything else as
+/// `RawData::Bytes`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizeNewlines {
+    pub target: NewlineStyle,
+}
+
+impl NormalizeNewlines {
+    pub fn new(target: NewlineStyle) -> Self {
+        Self { target }
+    }
+}
+
+fn normalize(src: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(src.len());
+    let mut i = 0;
+    while i < src.len() {
+        match src[i] {
+            b'\r' => {
+                out.extend_from_slice(target);
+                i += if src.get(i + 1) == Some(&b'\n') { 2 } else { 1 };
+            }
+            b'\n' => {
+                out.extend_from_slice(target);
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+impl PipeProcessor for NormalizeNewlines {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let out = normalize(data.as_bytes(), self.target.as_bytes());
+        if as_text(&data).is_some() {
+            // Only ASCII line-break bytes were replaced, so UTF-8 stays valid.
+            let text = String::from_utf8(out).expect("line-break rewrite keeps UTF-8 valid");
+            Ok(RawData::String(text))
+        } else {
+            Ok(RawData::Bytes(Bytes::from(out)))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "normalize_newlines"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(target: NewlineStyle, input: RawData) -> RawData {
+        NormalizeNewlines::new(target).process(input).unwrap()
+    }
+
+    #[test]
+    fn test_mixed_endings_each_target() {
+        let input = "a\r\nb\nc\rd\r\r\ne";
+        for (target, expected) in [
+            (NewlineStyle::Lf, "a\nb\nc\nd\n\ne"),
+            (NewlineStyle::CrLf, "a\r\nb\r\nc\r\nd\r\n\r\ne"),
+            (NewlineStyle::Cr, "a\rb\rc\rd\r\re"),
+        ] {
+            let out = run(target, RawData::from_string(input));
+            assert!(matches!(&out, RawData::String(s) if s == expected));
+        }
+
+        let crlf = run(NewlineStyle::CrLf, RawData::from_string("x\r\n"));
+        assert!(NormalizeNewlines::default().is_idempotent());
+        assert_eq!(run(NewlineStyle::CrLf, crlf).as_bytes(), b"x\r\n");
+    }
+
+    #[test]
+    fn test_binary_input_stays_bytes() {
+        let out = run(
+            NewlineStyle::Lf,
+            RawData::Bytes(Bytes::from_static(b"\xff\r\n\xfe")),
+        );
+        assert!(matches!(&out, RawData::Bytes(b) if &b[..] == b"\xff\n\xfe"));
+    }
+}
@@ -0,0 +1,191 @@
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::{PipeProcessor, RawDataKind, WparseResult};
+
+/// Maximum encoded line length, excluding the line break (RFC 2045 §6.7).
+const MAX_LINE: usize = 76;
+
+/// Decodes quoted-printable (RFC 2045) into `RawData::Bytes`.
+///
+/// Handles `=XX` escapes and soft line breaks (`=\r\n` or `=\n`); any other
+/// use of `=` fails with `FormatError`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotedPrintableDecode;
+
+/// Encodes into quoted-printable as `RawData::String`, wrapping lines with
+/// soft breaks at 76 characters. Existing `\r\n`/`\n` line breaks are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotedPrintableEncode;
+
+fn hex_val(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|v| v as u8)
+}
+
+fn format_err(msg: String) -> crate::WparseError {
+    DataErrKind::FormatError(msg, None).into()
+}
+
+fn qp_decode(src: &[u8]) -> WparseResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(src.len());
+    let mut i = 0;
+    while i < src.len() {
+        if src[i] != b'=' {
+            out.push(src[i]);
+            i += 1;
+            continue;
+        }
+        match &src[i + 1..] {
+            [b'\r', b'\n', ..] => i += 3,
+            [b'\n', ..] => i += 2,
+            [hi, lo, ..] => match (hex_val(*hi), hex_val(*lo)) {
+                (Some(hi), Some(lo)) => {
+                    out.push(hi << 4 | lo);
+                    i += 3;
+                }
+                _ => {
+                    return Err(format_err(format!(
+                        "qp_decode: invalid escape at offset {}",
+                        i
+                    )));
+                }
+            },
+            _ => {
+                return Err(format_err(format!(
+                    "qp_decode: truncated escape at offset {}",
+                    i
+                )));
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn qp_encode(src: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let mut out = String::with_capacity(src.len() + src.len() / 8);
+    let mut line_len = 0;
+    let mut i = 0;
+    while i < src.len() {
+        let b = src[i];
+        let line_break = match &src[i..] {
+            [b'\r', b'\n', ..] => Some(2),
+            [b'\n', ..] => Some(1),
+            _ => None,
+        };
+        if let Some(len) = line_break {
+            out.push_str(if len == 2 { "\r\n" } else { "\n" });
+            line_len = 0;
+            i += len;
+            continue;
+        }
+        let at_line_end = matches!(&src[i + 1..], [] | [b'\n', ..] | [b'\r', b'\n', ..]);
+        let literal =
+            (b'!'..=b'~').contains(&b) && b != b'=' || (b == b' ' || b == b'\t') && !at_line_end;
+        let width = if literal { 1 } else { 3 };
+        // Keep room for the trailing `=` of a soft break.
+        if line_len + width > MAX_LINE - 1 {
+            out.push_str("=\r\n");
+            line_len = 0;
+        }
+        if literal {
+            out.push(b as char);
+        } else {
+            out.push('=');
+            out.push(HEX[(b >> 4) as usize] as char);
+            out.push(HEX[(b & 0x0F) as usize] as char);
+        }
+        line_len += width;
+        i += 1;
+    }
+    out
+}
+
+impl PipeProcessor for QuotedPrintableDecode {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        qp_decode(data.as_bytes()).map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    fn name(&self) -> &'static str {
+        "qp_decode"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::String)
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+}
+
+impl PipeProcessor for QuotedPrintableEncode {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        Ok(RawData::String(qp_encode(data.as_bytes())))
+    }
+
+    fn name(&self) -> &'static str {
+        "qp_encode"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(text: &str) -> WparseResult<RawData> {
+        QuotedPrintableDecode.process(RawData::from_string(text))
+    }
+
+    #[test]
+    fn test_qp_decode() {
+        let out = decode("caf=C3=A9 soft=\r\nbreak=\nhere").unwrap();
+        assert_eq!(out.as_bytes(), "café softbreakhere".as_bytes());
+        assert!(matches!(out, RawData::Bytes(_)));
+    }
+
+    #[test]
+    fn test_qp_decode_malformed() {
+        assert!(decode("bad=ZZ").is_err());
+        assert!(decode("trailing=").is_err());
+        assert!(decode("short=4").is_err());
+    }
+
+    #[test]
+    fn test_qp_encode_round_trip() {
+        let src = "café = ok \r\nline two\t\n".repeat(6);
+        let encoded = QuotedPrintableEncode
+            .process(RawData::from_string(src.as_str()))
+            .unwrap();
+        let text = encoded.to_string();
+        assert!(text.starts_with("caf=C3=A9 =3D ok=20\r\n"));
+        assert!(text.split("\r\n").all(|line| line.len() <= MAX_LINE));
+
+        let decoded = QuotedPrintableDecode.process(encoded).unwrap();
+        assert_eq!(decoded.as_bytes(), src.as_bytes());
+    }
+
+    #[test]
+    fn test_qp_encode_wraps_long_lines() {
+        let src = "x".repeat(200);
+        let encoded = QuotedPrintableEncode
+            .process(RawData::from_string(src.as_str()))
+            .unwrap()
+            .to_string();
+        assert!(encoded.split("\r\n").all(|line| line.len() <= MAX_LINE));
+        assert_eq!(decode(&encoded).unwrap().as_bytes(), src.as_bytes());
+    }
+}
@@ -0,0 +1,361 @@
+use std::time::Duration;
+
+use wp_model_core::raw::RawData;
+
+use crate::{ParseContext, PipeHold, PipeProcessor, RawDataKind, WparseErrorExt, WparseResult};
+
+/// Delay between retry attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryBackoff {
+    Fixed(Duration),
+    /// `base_ms * 2^(retry - 1)`, capped at `max_ms`.
+    Exponential {
+        base_ms: u64,
+        max_ms: u64,
+    },
+}
+
+impl RetryBackoff {
+    /// Delay before the `retry`-th retry (1-based).
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            RetryBackoff::Fixed(delay) => delay,
+            RetryBackoff::Exponential { base_ms, max_ms } => {
+                let factor = 1u64
+                    .checked_shl(retry.saturating_sub(1))
+                    .unwrap_or(u64::MAX);
+                Duration::from_millis(base_ms.saturating_mul(factor).min(max_ms))
+            }
+        }
+    }
+}
+
+/// How often and how patiently a failing step is re-invoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one; `0` behaves like `1`.
+    pub max_attempts: u32,
+    pub backoff: RetryBackoff,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, backoff: RetryBackoff) -> Self {
+        Self {
+            max_attempts,
+            backoff,
+        }
+    }
+}
+
+/// Re-invokes the inner processor on retriable errors.
+///
+/// Only errors for which `WparseErrorExt::is_retriable` is `true` are retried;
+/// anything else is returned immediately. The input is cloned for every
+/// attempt but the last, which is cheap for `Bytes`/`ArcBytes`; with
+/// `max_attempts <= 1` it is never cloned. Backoff sleeps the calling thread.
+#[derive(Clone)]
+pub struct RetryingProcessor {
+    inner: PipeHold,
+    policy: RetryPolicy,
+}
+
+impl RetryingProcessor {
+    pub fn new(inner: PipeHold, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+impl RetryingProcessor {
+    fn attempt<T>(
+        &self,
+        data: RawData,
+        step: impl Fn(RawData) -> WparseResult<T>,
+    ) -> WparseResult<T> {
+        let attempts = self.policy.max_attempts.max(1);
+        for retry in 1..attempts {
+            match step(data.clone()) {
+                Err(err) if err.is_retriable() => self.backoff(retry),
+                result => return result,
+            }
+        }
+        // The last attempt gets the input itself.
+        step(data)
+    }
+
+    fn backoff(&self, retry: u32) {
+        let delay = self.policy.backoff.delay(retry);
+        if !delay.is_zero() {
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+impl PipeProcessor for RetryingProcessor {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        self.attempt(data, |data| self.inner.process(data))
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn process_split(&self, data: RawData) -> WparseResult<(RawData, Option<RawData>)> {
+        self.attempt(data, |data| self.inner.process_split(data))
+    }
+
+    fn process_with(&self, data: RawData, ctx: &ParseContext) -> WparseResult<RawData> {
+        self.attempt(data, |data| self.inner.process_with(data, ctx))
+    }
+
+    /// Runs the inner `process_in_place` directly. When retries are possible
+    /// a copy of the input is kept to restore `data` after a failed attempt.
+    fn process_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+        let attempts = self.policy.max_attempts.max(1);
+        let mut original = (attempts > 1).then(|| data.clone());
+        for retry in 1..attempts {
+            match self.inner.process_in_place(data) {
+                Err(err) if err.is_retriable() => {
+                    let restore = if retry + 1 < attempts {
+                        original.clone()
+                    } else {
+                        original.take()
+                    };
+                    *data = restore.expect("input kept while retries remain");
+                    self.backoff(retry);
+                }
+                result => return result,
+            }
+        }
+        self.inner.process_in_place(data)
+    }
+
+    fn can_process(&self) -> bool {
+        self.inner.can_process()
+    }
+
+    fn is_pure(&self) -> bool {
+        self.inner.is_pure()
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.inner.is_idempotent()
+    }
+
+    fn accepts_empty(&self) -> bool {
+        self.inner.accepts_empty()
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.inner.is_passthrough()
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        self.inner.input_type_hint()
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        self.inner.output_type_hint()
+    }
+
+    fn identity_key(&self) -> Option<String> {
+        let key = self.inner.identity_key()?;
+        Some(format!("retry({:?},{})", self.policy, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WparseReason;
+    use orion_error::conversion::ToStructError;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Flaky {
+        failures: u32,
+        calls: AtomicU32,
+        reason: WparseReason,
+    }
+
+    impl Flaky {
+        fn new(failures: u32, reason: WparseReason) -> Arc<Self> {
+            Arc::new(Self {
+                failures,
+                calls: AtomicU32::new(0),
+                reason,
+            })
+        }
+    }
+
+    impl PipeProcessor for Flaky {
+        fn process(&self, data: RawData) -> WparseResult<RawData> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(self.reason.clone().to_err());
+            }
+            Ok(data)
+        }
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+    }
+
+    const NO_WAIT: RetryBackoff = RetryBackoff::Fixed(Duration::ZERO);
+
+    #[test]
+    fn test_retry_until_success() {
+        let flaky = Flaky::new(2, WparseReason::network_error());
+        let retrying = RetryingProcessor::new(flaky.clone(), RetryPolicy::new(3, NO_WAIT));
+
+        let out = retrying.process(RawData::from_string("ok")).unwrap();
+        assert_eq!(out.as_bytes(), b"ok");
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_max_attempts() {
+        let flaky = Flaky::new(5, WparseReason::network_error());
+        let retrying = RetryingProcessor::new(flaky.clone(), RetryPolicy::new(2, NO_WAIT));
+
+        assert!(retrying.process(RawData::from_string("x")).is_err());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_non_retriable_error_is_not_retried() {
+        let flaky = Flaky::new(1, WparseReason::not_match());
+        let retrying = RetryingProcessor::new(flaky.clone(), RetryPolicy::new(5, NO_WAIT));
+
+        assert!(retrying.process(RawData::from_string("x")).is_err());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_last_attempt_moves_input() {
+        let text = String::from("payload");
+        let ptr = text.as_ptr();
+
+        let once = RetryingProcessor::new(
+            Flaky::new(0, WparseReason::network_error()),
+            RetryPolicy::new(1, NO_WAIT),
+        );
+        let out = once.process(RawData::String(text)).unwrap();
+        assert!(matches!(&out, RawData::String(s) if s.as_ptr() == ptr));
+
+        // After two failed attempts on clones, the third gets the original.
+        let flaky = Flaky::new(2, WparseReason::network_error());
+        let retrying = RetryingProcessor::new(flaky.clone(), RetryPolicy::new(3, NO_WAIT));
+        let out = retrying.process(out).unwrap();
+        assert!(matches!(&out, RawData::String(s) if s.as_ptr() == ptr));
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_in_place_retries_from_original_input() {
+        /// Appends a byte in place, failing (after mutating) on early calls.
+        struct Append {
+            failures: u32,
+            calls: AtomicU32,
+        }
+
+        impl PipeProcessor for Append {
+            fn process(&self, _data: RawData) -> WparseResult<RawData> {
+                panic!("in-place path expected");
+            }
+
+            fn name(&self) -> &'static str {
+                "append"
+            }
+
+            fn process_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+                let RawData::String(text) = data else {
+                    panic!("string input expected");
+                };
+                text.push('!');
+                if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                    return Err(WparseReason::network_error().to_err());
+                }
+                Ok(())
+            }
+        }
+
+        let append = Arc::new(Append {
+            failures: 2,
+            calls: AtomicU32::new(0),
+        });
+        let retrying = RetryingProcessor::new(append.clone(), RetryPolicy::new(3, NO_WAIT));
+        let mut data = RawData::from_string("hi");
+        retrying.process_in_place(&mut data).unwrap();
+        assert_eq!(data.as_bytes(), b"hi!");
+        assert_eq!(append.calls.load(Ordering::SeqCst), 3);
+
+        let once = RetryingProcessor::new(
+            Arc::new(Append {
+                failures: 1,
+                calls: AtomicU32::new(0),
+            }),
+            RetryPolicy::new(1, NO_WAIT),
+        );
+        let mut data = RawData::from_string("hi");
+        assert!(once.process_in_place(&mut data).is_err());
+    }
+
+    #[test]
+    fn test_retried_step_sees_context() {
+        use crate::Pipeline;
+
+        /// Replaces the payload with the context's `&'static str` extension.
+        struct FromContext;
+
+        impl PipeProcessor for FromContext {
+            fn process(&self, data: RawData) -> WparseResult<RawData> {
+                Ok(data)
+            }
+
+            fn name(&self) -> &'static str {
+                "from_context"
+            }
+
+            fn process_with(&self, data: RawData, ctx: &ParseContext) -> WparseResult<RawData> {
+                Ok(ctx.get::<&'static str>().map_or(data, RawData::from_string))
+            }
+        }
+
+        let ctx = ParseContext::new();
+        ctx.insert("CTX");
+        let pipeline = Pipeline::new()
+            .with(Arc::new(FromContext))
+            .with_retry(RetryPolicy::new(2, NO_WAIT));
+        let out = pipeline
+            .run_with(RawData::from_string("input"), &ctx)
+            .unwrap();
+        assert_eq!(out.as_bytes(), b"CTX");
+    }
+
+    #[test]
+    fn test_purity_follows_inner() {
+        use crate::processors::Strip;
+
+        let flaky = RetryingProcessor::new(
+            Flaky::new(0, WparseReason::not_match()),
+            RetryPolicy::new(2, NO_WAIT),
+        );
+        assert!(!flaky.is_pure());
+        let strip =
+            RetryingProcessor::new(Arc::new(Strip::new(1, 0)), RetryPolicy::new(2, NO_WAIT));
+        assert!(strip.is_pure());
+        assert!(!strip.is_idempotent());
+    }
+
+    #[test]
+    fn test_exponential_backoff_is_capped() {
+        let backoff = RetryBackoff::Exponential {
+            base_ms: 10,
+            max_ms: 50,
+        };
+        assert_eq!(backoff.delay(1), Duration::from_millis(10));
+        assert_eq!(backoff.delay(3), Duration::from_millis(40));
+        assert_eq!(backoff.delay(4), Duration::from_millis(50));
+        assert_eq!(backoff.delay(200), Duration::from_millis(50));
+    }
+}
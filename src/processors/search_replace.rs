@@ -0,0 +1,72 @@
+use wp_model_core::raw::RawData;
+
+use crate::{PipeProcessor, RawDataExt, RawDataKind, WparseReason, WparseResult};
+
+/// Replaces every non-overlapping occurrence of `find` with `replace`.
+///
+/// Output is always `RawData::Bytes`. An empty `find` is a configuration
+/// error reported as `WparseReason::Encoding`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchReplace {
+    pub find: Vec<u8>,
+    pub replace: Vec<u8>,
+}
+
+impl SearchReplace {
+    pub fn new(find: impl Into<Vec<u8>>, replace: impl Into<Vec<u8>>) -> Self {
+        Self {
+            find: find.into(),
+            replace: replace.into(),
+        }
+    }
+}
+
+impl PipeProcessor for SearchReplace {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        if self.find.is_empty() {
+            return Err(
+                WparseReason::Encoding("search_replace: empty pattern".into()).into_traced_err(),
+            );
+        }
+        Ok(data.replace_all(&self.find, &self.replace))
+    }
+
+    fn name(&self) -> &'static str {
+        "search_replace"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+
+    fn can_process(&self) -> bool {
+        !self.find.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_replace_processor() {
+        let proc = SearchReplace::new(b"\r\n".as_slice(), b"\n".as_slice());
+        let out = proc.process(RawData::from_string("a\r\nb\r\n")).unwrap();
+        assert_eq!(out.as_bytes(), b"a\nb\n");
+
+        let out = proc.process(RawData::from_string("plain")).unwrap();
+        assert_eq!(out.as_bytes(), b"plain");
+    }
+
+    #[test]
+    fn test_search_replace_empty_find() {
+        let proc = SearchReplace::new(Vec::new(), b"x".as_slice());
+        assert!(!proc.can_process());
+        let err = proc.process(RawData::from_string("abc")).unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Encoding(_)));
+    }
+}
@@ -0,0 +1,110 @@
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::raw_ext::shared_slice;
+use crate::{PipeProcessor, WparseReason, WparseResult};
+
+/// Removes a fixed-size envelope: the first `head` and last `tail` bytes.
+///
+/// `Bytes` and `ArcBytes` inputs are sliced without copying. Inputs shorter
+/// than `head + tail` fail with `LessData`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strip {
+    pub head: usize,
+    pub tail: usize,
+}
+
+impl Strip {
+    pub fn new(head: usize, tail: usize) -> Self {
+        Self { head, tail }
+    }
+}
+
+impl PipeProcessor for Strip {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let envelope = self.head.checked_add(self.tail);
+        match envelope {
+            Some(size) if size <= data.len() => {
+                Ok(shared_slice(&data, self.head..data.len() - self.tail))
+            }
+            _ => Err(DataErrKind::LessData.into()),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "strip"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn accepts_empty(&self) -> bool {
+        self.head == 0 && self.tail == 0
+    }
+
+    fn identity_key(&self) -> Option<String> {
+        Some(format!("strip({},{})", self.head, self.tail))
+    }
+
+    fn set_config(&mut self, key: &str, value: &str) -> WparseResult<()> {
+        let slot = match key {
+            "head" => &mut self.head,
+            "tail" => &mut self.tail,
+            _ => return Ok(()),
+        };
+        *slot = value.parse().map_err(|_| {
+            WparseReason::core_conf()
+                .into_traced_err()
+                .with_detail(format!("strip: invalid {}: {}", key, value))
+        })?;
+        Ok(())
+    }
+
+    fn get_config(&self, key: &str) -> Option<String> {
+        match key {
+            "head" => Some(self.head.to_string()),
+            "tail" => Some(self.tail.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WparseReason;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_strip_envelope() {
+        let strip = Strip::new(2, 1);
+        let out = strip.process(RawData::from_string("[[body]")).unwrap();
+        assert_eq!(out.as_bytes(), b"body");
+
+        let arc = Arc::new(b"HDpayloadT".to_vec());
+        let out = strip.process(RawData::from_arc_bytes(arc.clone())).unwrap();
+        assert_eq!(out.as_bytes(), b"payload");
+        assert_eq!(out.as_bytes().as_ptr(), arc[2..].as_ptr());
+
+        let out = Strip::new(3, 0)
+            .process(RawData::from_string("abc"))
+            .unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_strip_less_data() {
+        let err = Strip::new(3, 2)
+            .process(RawData::from_string("abcd"))
+            .unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::data_error());
+        assert_eq!(err.detail().as_deref(), Some("less data"));
+
+        assert!(
+            Strip::new(usize::MAX, 1)
+                .process(RawData::from_string("x"))
+                .is_err()
+        );
+    }
+}
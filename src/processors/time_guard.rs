@@ -0,0 +1,189 @@
+use std::sync::mpsc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::{PipeHold, PipeProcessor, RawDataKind, WparseReason, WparseResult};
+
+/// Fails with `WparseReason::Timeout` when the inner processor does not
+/// finish within `limit`.
+///
+/// Each call runs the inner `process`, `process_split` or `process_in_place`
+/// on a fresh worker thread and waits for at most `limit`. Rust cannot cancel
+/// a running thread, so on timeout the worker is detached and keeps running
+/// until the inner processor returns; its result is discarded. Bounding the
+/// actual CPU or memory spent requires the processor to cooperate (e.g. by
+/// checking a deadline itself). The timeout error carries the inner step
+/// name; a panic in the inner processor is reported as `StepFailed`.
+///
+/// `process_with` is not forwarded: the borrowed [`ParseContext`] cannot move
+/// to a worker that may outlive the call, so a guarded step runs its plain
+/// `process` and does not see the context.
+///
+/// [`ParseContext`]: crate::ParseContext
+#[derive(Clone)]
+pub struct TimeGuard {
+    inner: PipeHold,
+    limit: Duration,
+}
+
+impl TimeGuard {
+    pub fn new(inner: PipeHold, limit: Duration) -> Self {
+        Self { inner, limit }
+    }
+
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    /// Run `job` on the inner processor in a worker thread, waiting at most
+    /// `limit` for its result.
+    fn guarded<T, F>(&self, job: F) -> WparseResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&PipeHold) -> WparseResult<T> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let inner = self.inner.clone();
+        std::thread::spawn(move || {
+            // The receiver is gone after a timeout; the result is dropped.
+            let _ = tx.send(job(&inner));
+        });
+        match rx.recv_timeout(self.limit) {
+            Ok(result) => result,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                Err(WparseReason::Timeout(self.inner.name().to_string())
+                    .into_traced_err()
+                    .with_detail(format!("exceeded {:?}", self.limit)))
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                Err(WparseReason::StepFailed(self.inner.name().to_string())
+                    .into_traced_err()
+                    .with_detail("processor panicked"))
+            }
+        }
+    }
+}
+
+impl PipeProcessor for TimeGuard {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        self.guarded(move |inner| inner.process(data))
+    }
+
+    fn process_split(&self, data: RawData) -> WparseResult<(RawData, Option<RawData>)> {
+        self.guarded(move |inner| inner.process_split(data))
+    }
+
+    /// The payload moves to the worker and back; after a timeout or panic
+    /// `data` is left empty.
+    fn process_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+        let mut owned = std::mem::replace(data, RawData::Bytes(Bytes::new()));
+        *data = self.guarded(move |inner| {
+            inner.process_in_place(&mut owned)?;
+            Ok(owned)
+        })?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn can_process(&self) -> bool {
+        self.inner.can_process()
+    }
+
+    fn is_pure(&self) -> bool {
+        self.inner.is_pure()
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.inner.is_idempotent()
+    }
+
+    fn accepts_empty(&self) -> bool {
+        self.inner.accepts_empty()
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.inner.is_passthrough()
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        self.inner.input_type_hint()
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        self.inner.output_type_hint()
+    }
+
+    fn identity_key(&self) -> Option<String> {
+        let key = self.inner.identity_key()?;
+        Some(format!("time_guard({:?},{})", self.limit, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WparseErrorExt;
+    use crate::processors::Strip;
+    use std::sync::Arc;
+
+    struct Stall;
+
+    impl PipeProcessor for Stall {
+        fn process(&self, data: RawData) -> WparseResult<RawData> {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(data)
+        }
+
+        fn name(&self) -> &'static str {
+            "stall"
+        }
+    }
+
+    #[test]
+    fn test_fast_processor_passes_through() {
+        let guard = TimeGuard::new(Arc::new(Strip::new(1, 1)), Duration::from_secs(5));
+        let out = guard.process(RawData::from_string("[x]")).unwrap();
+        assert_eq!(out.as_bytes(), b"x");
+        assert!(guard.process(RawData::from_string("")).is_err());
+    }
+
+    #[test]
+    fn test_split_and_in_place_go_through_guard() {
+        use crate::Pipeline;
+        use crate::processors::Cap;
+
+        let pipeline = Pipeline::new().with(Arc::new(TimeGuard::new(
+            Arc::new(Cap::new(2)),
+            Duration::from_secs(5),
+        )));
+        let (out, rest) = pipeline.run_split(RawData::from_string("abcdef")).unwrap();
+        assert_eq!(out.as_bytes(), b"ab");
+        assert_eq!(rest.len(), 1);
+        assert_eq!((rest[0].0, rest[0].1.as_bytes()), (0, &b"cdef"[..]));
+
+        let guard = TimeGuard::new(Arc::new(Strip::new(1, 1)), Duration::from_secs(5));
+        let mut data = RawData::from_string("[x]");
+        guard.process_in_place(&mut data).unwrap();
+        assert_eq!(data.as_bytes(), b"x");
+
+        let guard = TimeGuard::new(Arc::new(Stall), Duration::from_millis(10));
+        let mut data = RawData::from_string("x");
+        let err = guard.process_in_place(&mut data).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::Timeout("stall".into()));
+        assert!(data.is_empty());
+        assert!(guard.process_split(RawData::from_string("x")).is_err());
+    }
+
+    #[test]
+    fn test_slow_processor_times_out() {
+        let guard = TimeGuard::new(Arc::new(Stall), Duration::from_millis(10));
+        let err = guard.process(RawData::from_string("x")).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::Timeout("stall".into()));
+        assert!(err.is_retriable());
+    }
+}
@@ -0,0 +1,214 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use wp_model_core::raw::RawData;
+
+use crate::{ParseContext, PipeHold, PipeProcessor, RawDataKind, WparseResult};
+
+/// Latency summary collected by a [`TimedProcessor`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimingStats {
+    pub calls: u64,
+    /// Calls that took longer than the budget.
+    pub over_budget: u64,
+    pub total: Duration,
+    /// Fastest call; zero before the first call.
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl TimingStats {
+    /// Mean latency per call; zero before the first call.
+    pub fn mean(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(self.calls as f64)
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration, over_budget: bool) {
+        self.min = if self.calls == 0 {
+            elapsed
+        } else {
+            self.min.min(elapsed)
+        };
+        self.max = self.max.max(elapsed);
+        self.calls += 1;
+        self.total += elapsed;
+        if over_budget {
+            self.over_budget += 1;
+        }
+    }
+}
+
+type OverBudgetFn = Arc<dyn Fn(&str, Duration) + Send + Sync>;
+
+/// Measures every call of the inner processor against a soft time budget.
+///
+/// Calls are never interrupted: a call that overruns `budget` still returns
+/// its result, and the optional callback set with
+/// [`on_over_budget`](Self::on_over_budget) is invoked with the step name and
+/// elapsed time. Failed calls are timed too. Clones share the same stats.
+#[derive(Clone)]
+pub struct TimedProcessor {
+    inner: PipeHold,
+    budget: Duration,
+    on_over_budget: Option<OverBudgetFn>,
+    stats: Arc<Mutex<TimingStats>>,
+}
+
+impl TimedProcessor {
+    pub fn new(inner: PipeHold, budget: Duration) -> Self {
+        Self {
+            inner,
+            budget,
+            on_over_budget: None,
+            stats: Arc::default(),
+        }
+    }
+
+    /// Call `f(step_name, elapsed)` after every call that exceeds the budget.
+    pub fn on_over_budget<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, Duration) + Send + Sync + 'static,
+    {
+        self.on_over_budget = Some(Arc::new(f));
+        self
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Snapshot of the stats collected so far.
+    pub fn stats(&self) -> TimingStats {
+        *self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn timed<T>(&self, step: impl FnOnce() -> WparseResult<T>) -> WparseResult<T> {
+        let start = Instant::now();
+        let result = step();
+        let elapsed = start.elapsed();
+        let over_budget = elapsed > self.budget;
+        self.stats
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(elapsed, over_budget);
+        if over_budget && let Some(f) = &self.on_over_budget {
+            f(self.inner.name(), elapsed);
+        }
+        result
+    }
+}
+
+impl PipeProcessor for TimedProcessor {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        self.timed(|| self.inner.process(data))
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn process_split(&self, data: RawData) -> WparseResult<(RawData, Option<RawData>)> {
+        self.timed(|| self.inner.process_split(data))
+    }
+
+    fn process_with(&self, data: RawData, ctx: &ParseContext) -> WparseResult<RawData> {
+        self.timed(|| self.inner.process_with(data, ctx))
+    }
+
+    fn process_in_place(&self, data: &mut RawData) -> WparseResult<()> {
+        self.timed(|| self.inner.process_in_place(data))
+    }
+
+    fn can_process(&self) -> bool {
+        self.inner.can_process()
+    }
+
+    fn is_pure(&self) -> bool {
+        self.inner.is_pure()
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.inner.is_idempotent()
+    }
+
+    fn accepts_empty(&self) -> bool {
+        self.inner.accepts_empty()
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.inner.is_passthrough()
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        self.inner.input_type_hint()
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        self.inner.output_type_hint()
+    }
+
+    fn identity_key(&self) -> Option<String> {
+        let key = self.inner.identity_key()?;
+        Some(format!("timed({:?},{})", self.budget, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::Strip;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Slow;
+
+    impl PipeProcessor for Slow {
+        fn process(&self, data: RawData) -> WparseResult<RawData> {
+            std::thread::sleep(Duration::from_millis(5));
+            Ok(data)
+        }
+
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+    }
+
+    #[test]
+    fn test_stats_count_every_call() {
+        let timed = TimedProcessor::new(Arc::new(Strip::new(1, 0)), Duration::from_secs(60));
+        for _ in 0..5 {
+            timed.process(RawData::from_string("xy")).unwrap();
+        }
+        assert!(timed.process(RawData::from_string("")).is_err());
+
+        let stats = timed.stats();
+        assert_eq!(stats.calls, 6);
+        assert_eq!(stats.over_budget, 0);
+        assert!(stats.min <= stats.max);
+        assert!(stats.total >= stats.max);
+        assert_eq!(timed.name(), "strip");
+    }
+
+    #[test]
+    fn test_over_budget_calls_back_but_returns_result() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let seen = hits.clone();
+        let timed = TimedProcessor::new(Arc::new(Slow), Duration::from_millis(1)).on_over_budget(
+            move |name, elapsed| {
+                assert_eq!(name, "slow");
+                assert!(elapsed > Duration::from_millis(1));
+                seen.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        let out = timed.process(RawData::from_string("data")).unwrap();
+        assert_eq!(out.as_bytes(), b"data");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        let stats = timed.clone().stats();
+        assert_eq!((stats.calls, stats.over_budget), (1, 1));
+        assert!(stats.mean() >= Duration::from_millis(4));
+    }
+}
@@ -0,0 +1,149 @@
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::raw_ext::shared_slice;
+use crate::{PipeProcessor, RawDataKind, WparseReason, WparseResult};
+
+/// Longest LEB128 encoding of a `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+/// Decode the unsigned LEB128 (protobuf) varint at the start of `bytes`,
+/// returning the value and the number of header bytes it used.
+///
+/// Input ending inside the varint fails with `LessData`; encodings longer
+/// than ten bytes or overflowing a `u64` fail with `FormatError`.
+pub fn read_varint(bytes: &[u8]) -> WparseResult<(u64, usize)> {
+    let mut value = 0u64;
+    for (i, &b) in bytes.iter().take(MAX_VARINT_LEN).enumerate() {
+        if i == MAX_VARINT_LEN - 1 && b > 1 {
+            return Err(varint_error("varint overflows u64"));
+        }
+        value |= u64::from(b & 0x7f) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    if bytes.len() >= MAX_VARINT_LEN {
+        Err(varint_error("varint longer than 10 bytes"))
+    } else {
+        Err(DataErrKind::LessData.into())
+    }
+}
+
+fn varint_error(what: &str) -> crate::WparseError {
+    DataErrKind::FormatError(what.to_string(), None).into()
+}
+
+/// Removes a leading varint length and returns the payload it frames.
+///
+/// The length is read with [`read_varint`]. A payload shorter than declared
+/// fails with `WparseReason::Truncated`. `process` rejects trailing bytes
+/// with `FormatError`, like `LengthPrefixStrip`; `process_split` instead
+/// hands them back as the remainder, so a stream of length-delimited
+/// messages can be walked with [`Pipeline::run_split`](crate::Pipeline::run_split).
+/// The payload is sliced without copying for `Bytes`/`ArcBytes`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VarintPrefixStrip;
+
+impl VarintPrefixStrip {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn frame(&self, data: &RawData) -> WparseResult<(usize, usize)> {
+        let bytes = data.as_bytes();
+        let (declared, header) = read_varint(bytes)?;
+        let available = (bytes.len() - header) as u64;
+        if available < declared {
+            return Err(WparseReason::Truncated(format!(
+                "varint_prefix_strip: declared {} payload bytes, got {}",
+                declared, available
+            ))
+            .into_traced_err());
+        }
+        Ok((header, header + declared as usize))
+    }
+}
+
+impl PipeProcessor for VarintPrefixStrip {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let (start, end) = self.frame(&data)?;
+        if end < data.len() {
+            return Err(DataErrKind::FormatError(
+                format!(
+                    "varint_prefix_strip: {} trailing bytes after declared payload",
+                    data.len() - end
+                ),
+                None,
+            )
+            .into());
+        }
+        Ok(shared_slice(&data, start..end))
+    }
+
+    fn name(&self) -> &'static str {
+        "varint_prefix_strip"
+    }
+
+    fn process_split(&self, data: RawData) -> WparseResult<(RawData, Option<RawData>)> {
+        let (start, end) = self.frame(&data)?;
+        let rest = (end < data.len()).then(|| shared_slice(&data, end..data.len()));
+        Ok((shared_slice(&data, start..end), rest))
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn accepts_empty(&self) -> bool {
+        false
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_read_varint() {
+        assert_eq!(read_varint(b"\x00").unwrap(), (0, 1));
+        assert_eq!(read_varint(b"\x96\x01rest").unwrap(), (150, 2));
+        let max = b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01";
+        assert_eq!(read_varint(max).unwrap(), (u64::MAX, 10));
+
+        for truncated in [&b""[..], b"\x80", b"\xff\xff"] {
+            let err = read_varint(truncated).unwrap_err();
+            assert_eq!(err.detail().as_deref(), Some("less data"));
+        }
+        for malformed in [
+            &b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"[..],
+            &[0x80; 11],
+        ] {
+            let err = read_varint(malformed).unwrap_err();
+            assert!(err.detail().as_deref().unwrap().starts_with("format error"));
+        }
+    }
+
+    #[test]
+    fn test_strip_and_split_stream() {
+        let strip = VarintPrefixStrip::new();
+        let out = strip.process(RawData::from_string("\x05hello")).unwrap();
+        assert_eq!(out.as_bytes(), b"hello");
+
+        let stream = RawData::Bytes(Bytes::from_static(b"\x02hi\x03abc"));
+        assert!(strip.process(stream.clone()).is_err());
+        let (first, rest) = strip.process_split(stream).unwrap();
+        assert_eq!(first.as_bytes(), b"hi");
+        let (second, rest) = strip.process_split(rest.unwrap()).unwrap();
+        assert_eq!(second.as_bytes(), b"abc");
+        assert!(rest.is_none());
+
+        let err = strip.process(RawData::from_string("\x05hi")).unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Truncated(msg) if msg.contains("declared 5")));
+    }
+}
@@ -0,0 +1,85 @@
+use wp_model_core::raw::RawData;
+
+use crate::raw_ext::as_text;
+use crate::{PipeProcessor, RawDataKind, WparseReason, WparseResult};
+
+/// Replaces every run of Unicode whitespace with a single ASCII space,
+/// optionally dropping leading and trailing whitespace altogether.
+///
+/// Output is always `RawData::String`; input that is not valid UTF-8 fails
+/// with `WparseReason::Encoding`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollapseWhitespace {
+    pub trim: bool,
+}
+
+impl CollapseWhitespace {
+    pub fn new(trim: bool) -> Self {
+        Self { trim }
+    }
+}
+
+impl PipeProcessor for CollapseWhitespace {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let text = as_text(&data).ok_or_else(|| {
+            WparseReason::Encoding("collapse_whitespace: input is not valid UTF-8".into())
+                .into_traced_err()
+        })?;
+        let text = if self.trim { text.trim() } else { text };
+
+        let mut out = String::with_capacity(text.len());
+        let mut in_run = false;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !in_run {
+                    out.push(' ');
+                }
+                in_run = true;
+            } else {
+                out.push(c);
+                in_run = false;
+            }
+        }
+        Ok(RawData::String(out))
+    }
+
+    fn name(&self) -> &'static str {
+        "collapse_whitespace"
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::String)
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_runs() {
+        let input = RawData::from_string("  a \t\n b\u{3000}\u{a0}c  ");
+        let out = CollapseWhitespace::new(false).process(input).unwrap();
+        assert!(matches!(&out, RawData::String(s) if s == " a b c "));
+
+        let input = RawData::Bytes(bytes::Bytes::from_static(b"\r\n key  = value \n"));
+        let out = CollapseWhitespace::new(true).process(input).unwrap();
+        assert!(matches!(&out, RawData::String(s) if s == "key = value"));
+    }
+
+    #[test]
+    fn test_collapse_rejects_invalid_utf8() {
+        let err = CollapseWhitespace::default()
+            .process(RawData::Bytes(bytes::Bytes::from_static(b"a\xffb")))
+            .unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Encoding(_)));
+    }
+}
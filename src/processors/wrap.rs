@@ -0,0 +1,90 @@
+use bytes::BytesMut;
+use wp_model_core::raw::RawData;
+
+use crate::{PipeProcessor, RawDataKind, WparseResult};
+
+/// Surrounds the payload with fixed bytes: `prefix + data + suffix`.
+///
+/// The inverse of [`Strip`](super::Strip). Output is `RawData::Bytes` built in
+/// a single pre-sized buffer; with both sides empty the input is returned
+/// unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Wrap {
+    pub prefix: Vec<u8>,
+    pub suffix: Vec<u8>,
+}
+
+impl Wrap {
+    pub fn new(prefix: impl Into<Vec<u8>>, suffix: impl Into<Vec<u8>>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            suffix: suffix.into(),
+        }
+    }
+}
+
+impl PipeProcessor for Wrap {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Ok(data);
+        }
+        let mut buf = BytesMut::with_capacity(self.prefix.len() + data.len() + self.suffix.len());
+        buf.extend_from_slice(&self.prefix);
+        buf.extend_from_slice(data.as_bytes());
+        buf.extend_from_slice(&self.suffix);
+        Ok(RawData::Bytes(buf.freeze()))
+    }
+
+    fn name(&self) -> &'static str {
+        "wrap"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn identity_key(&self) -> Option<String> {
+        Some(format!(
+            "wrap({},{})",
+            self.prefix.escape_ascii(),
+            self.suffix.escape_ascii()
+        ))
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        (!self.prefix.is_empty() || !self.suffix.is_empty()).then_some(RawDataKind::Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::Strip;
+
+    #[test]
+    fn test_wrap_round_trips_with_strip() {
+        let wrap = Wrap::new(b"<<".as_slice(), b">".as_slice());
+        let out = wrap.process(RawData::from_string("body")).unwrap();
+        assert!(matches!(&out, RawData::Bytes(b) if &b[..] == b"<<body>"));
+
+        let stripped = Strip::new(2, 1).process(out).unwrap();
+        assert_eq!(stripped.as_bytes(), b"body");
+
+        let only_suffix = Wrap::new(Vec::new(), b"\n".as_slice());
+        assert_eq!(
+            only_suffix
+                .process(RawData::from_string("x"))
+                .unwrap()
+                .as_bytes(),
+            b"x\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_wrap_is_noop() {
+        let out = Wrap::default()
+            .process(RawData::from_string("keep"))
+            .unwrap();
+        assert!(matches!(out, RawData::String(s) if s == "keep"));
+    }
+}
@@ -0,0 +1,133 @@
+use bytes::Bytes;
+use wp_model_core::raw::RawData;
+
+use crate::compression::{zlib_decode_limited, zlib_encode_level};
+use crate::{PipeProcessor, RawDataKind, WparseResult};
+
+/// Inflates a zlib stream (RFC 1950: 2-byte header, deflate data, Adler-32
+/// trailer) into `RawData::Bytes`.
+///
+/// A bad header, corrupt data or a checksum mismatch fails with
+/// `WparseReason::Encoding("zlib")`, as does output growing past
+/// `max_output` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZlibDecode {
+    pub max_output: usize,
+}
+
+impl ZlibDecode {
+    pub fn new(max_output: usize) -> Self {
+        Self { max_output }
+    }
+}
+
+impl Default for ZlibDecode {
+    /// 64 MiB output limit.
+    fn default() -> Self {
+        Self::new(64 << 20)
+    }
+}
+
+impl PipeProcessor for ZlibDecode {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        zlib_decode_limited(data.as_bytes(), self.max_output)
+            .map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    fn name(&self) -> &'static str {
+        "zlib_decode"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn accepts_empty(&self) -> bool {
+        false
+    }
+
+    fn input_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+}
+
+/// Deflates the payload into a zlib stream as `RawData::Bytes`.
+///
+/// `level` ranges from 0 (store) to 9 (best); larger values are clamped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZlibEncode {
+    pub level: u32,
+}
+
+impl ZlibEncode {
+    pub fn new(level: u32) -> Self {
+        Self { level }
+    }
+}
+
+impl Default for ZlibEncode {
+    fn default() -> Self {
+        Self::new(6)
+    }
+}
+
+impl PipeProcessor for ZlibEncode {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        zlib_encode_level(data.as_bytes(), self.level.min(9))
+            .map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    fn name(&self) -> &'static str {
+        "zlib_encode"
+    }
+
+    fn is_pure(&self) -> bool {
+        true
+    }
+
+    fn output_type_hint(&self) -> Option<RawDataKind> {
+        Some(RawDataKind::Bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WparseReason;
+
+    #[test]
+    fn test_zlib_round_trip() {
+        let input = RawData::from_string("abc abc abc abc abc abc");
+        let packed = ZlibEncode::default().process(input.clone()).unwrap();
+        // CMF byte: deflate with a 32 KiB window.
+        assert_eq!(packed.as_bytes()[0], 0x78);
+        let out = ZlibDecode::default().process(packed).unwrap();
+        assert_eq!(out.as_bytes(), input.as_bytes());
+    }
+
+    #[test]
+    fn test_zlib_decode_errors() {
+        let packed = ZlibEncode::new(9)
+            .process(RawData::Bytes(Bytes::from(vec![0u8; 4096])))
+            .unwrap();
+        let err = ZlibDecode::new(1024).process(packed.clone()).unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::Encoding("zlib".into()));
+        assert!(err.detail().as_deref().unwrap().contains("1024"));
+        assert_eq!(
+            ZlibDecode::new(4096).process(packed.clone()).unwrap().len(),
+            4096
+        );
+
+        let mut corrupt = packed.as_bytes().to_vec();
+        let last = corrupt.len() - 1;
+        corrupt[last] ^= 0xff;
+        let err = ZlibDecode::default()
+            .process(RawData::Bytes(Bytes::from(corrupt)))
+            .unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::Encoding("zlib".into()));
+    }
+}
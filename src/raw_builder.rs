@@ -0,0 +1,123 @@
+//! Incremental construction of [`RawData`] payloads.
+
+use bytes::BytesMut;
+use wp_model_core::raw::RawData;
+
+/// Growable buffer that finalizes into `RawData::Bytes`.
+///
+/// Collect generated payloads without an intermediate `Vec`:
+/// `let raw: RawData = (0u8..16).collect::<RawDataBuilder>().build();`.
+/// Collecting `RawData` items concatenates their bytes.
+#[derive(Debug, Clone, Default)]
+pub struct RawDataBuilder {
+    buf: BytesMut,
+}
+
+impl RawDataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.buf.extend_from_slice(&[byte]);
+    }
+
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Finish building; the buffer is frozen without copying.
+    pub fn build(self) -> RawData {
+        RawData::Bytes(self.buf.freeze())
+    }
+}
+
+impl Extend<u8> for RawDataBuilder {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.buf.extend(iter);
+    }
+}
+
+impl<'a> Extend<&'a u8> for RawDataBuilder {
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.buf.extend(iter.into_iter().copied());
+    }
+}
+
+impl Extend<RawData> for RawDataBuilder {
+    fn extend<I: IntoIterator<Item = RawData>>(&mut self, iter: I) {
+        for data in iter {
+            self.buf.extend_from_slice(data.as_bytes());
+        }
+    }
+}
+
+impl FromIterator<u8> for RawDataBuilder {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut builder = Self::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+impl FromIterator<RawData> for RawDataBuilder {
+    fn from_iter<I: IntoIterator<Item = RawData>>(iter: I) -> Self {
+        let mut builder = Self::new();
+        builder.extend(iter);
+        builder
+    }
+}
+
+impl From<RawDataBuilder> for RawData {
+    fn from(builder: RawDataBuilder) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_collect_bytes_into_raw() {
+        let raw = (b'a'..=b'e').collect::<RawDataBuilder>().build();
+        assert!(matches!(raw, RawData::Bytes(_)));
+        assert_eq!(raw.as_bytes(), b"abcde");
+    }
+
+    #[test]
+    fn test_collect_raw_concatenates() {
+        let parts = vec![
+            RawData::from_string("ab"),
+            RawData::from_arc_bytes(Arc::new(b"cd".to_vec())),
+            RawData::from_string(""),
+        ];
+        let raw: RawData = parts.into_iter().collect::<RawDataBuilder>().into();
+        assert_eq!(raw.as_bytes(), b"abcd");
+    }
+
+    #[test]
+    fn test_extend_builder() {
+        let mut builder = RawDataBuilder::with_capacity(8);
+        builder.push(b'[');
+        builder.extend(b"xy".iter());
+        builder.extend_from_slice(b"]");
+        assert_eq!(builder.len(), 4);
+        assert_eq!(builder.build().as_bytes(), b"[xy]");
+    }
+}
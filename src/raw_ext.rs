@@ -0,0 +1,2033 @@
+//! Extension methods for [`RawData`].
+//!
+//! `RawData` lives in `wp-model-core`; helpers that belong to the plugin API are
+//! provided through [`RawDataExt`]. Bring the trait into scope with
+//! `use wp_parse_api::RawDataExt;`.
+
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use wp_model_core::raw::RawData;
+
+use crate::checksum;
+use crate::error::DataErrKind;
+use crate::{Checksum, HexDump, RawDataDiff, RawDataWriter, WparseReason, WparseResult};
+
+/// Plugin-level helpers on top of [`RawData`].
+pub trait RawDataExt {
+    /// Zero-copy `RawData::Bytes` view of `arc[offset..offset + len]` that
+    /// shares ownership of `arc`.
+    ///
+    /// Fails with `OutOfBounds` if the range does not fit in the buffer.
+    fn from_arc_bytes_with_offset(
+        arc: Arc<Vec<u8>>,
+        offset: usize,
+        len: usize,
+    ) -> WparseResult<RawData>
+    where
+        Self: Sized;
+
+    /// `RawData::String` of `lines` joined with `\n` (no trailing newline).
+    fn from_lines(lines: &[&str]) -> RawData
+    where
+        Self: Sized;
+
+    /// Like [`from_lines`](Self::from_lines) but joined with `\r\n`.
+    fn from_lines_crlf(lines: &[&str]) -> RawData
+    where
+        Self: Sized;
+
+    /// `RawData::Bytes` of binary `lines` joined with `\n`.
+    fn from_lines_bytes(lines: &[&[u8]]) -> RawData
+    where
+        Self: Sized;
+
+    /// [`from_lines`](Self::from_lines) for any iterator of string-likes.
+    fn from_lines_iter<I>(iter: I) -> RawData
+    where
+        Self: Sized,
+        I: IntoIterator,
+        I::Item: AsRef<str>;
+
+    /// Start building a payload through `io::Write` / `fmt::Write`; same as
+    /// [`RawDataWriter::new`].
+    fn writer() -> RawDataWriter
+    where
+        Self: Sized;
+
+    /// Number of Unicode scalar values, or `None` if the payload is not valid UTF-8.
+    ///
+    /// Unlike `len()`, which counts bytes, this is suitable for display
+    /// truncation of text fields.
+    fn char_count(&self) -> Option<usize>;
+
+    /// The first `n` characters as `RawData::String`, or `None` if the payload
+    /// is not valid UTF-8. Returns the whole text when it has fewer than `n`
+    /// characters.
+    fn truncate_to_chars(&self, n: usize) -> Option<RawData>;
+
+    /// Bytes allocated for the payload.
+    ///
+    /// `String` and `ArcBytes` report their buffer capacity; `Bytes` does not
+    /// expose one and reports `len()`.
+    fn capacity(&self) -> usize;
+
+    /// Release spare capacity where the buffer is uniquely owned.
+    ///
+    /// `String` always shrinks. `ArcBytes` shrinks only when no other clone
+    /// of the `Arc` exists; shared buffers are left untouched. `Bytes` is a
+    /// no-op.
+    fn shrink_to_fit(&mut self);
+
+    /// Replace the first occurrence of `find` with `replace`.
+    ///
+    /// Always returns `RawData::Bytes`; the content is unchanged when `find`
+    /// is empty or absent.
+    fn replace_first(&self, find: &[u8], replace: &[u8]) -> RawData;
+
+    /// Replace every non-overlapping occurrence of `find`, scanning left to
+    /// right. Same fail-safe rules as [`replace_first`](Self::replace_first).
+    fn replace_all(&self, find: &[u8], replace: &[u8]) -> RawData;
+
+    /// Unicode uppercase of the text, or `None` if the payload is not valid UTF-8.
+    fn to_uppercase_string(&self) -> Option<String>;
+
+    /// Unicode lowercase of the text, or `None` if the payload is not valid UTF-8.
+    fn to_lowercase_string(&self) -> Option<String>;
+
+    /// Uppercase ASCII letters only, leaving every other byte untouched.
+    ///
+    /// Works on arbitrary binary data without UTF-8 validation and returns
+    /// `RawData::Bytes`.
+    fn ascii_uppercase(&self) -> RawData;
+
+    /// ASCII-lowercase copy that keeps the `String` variant for text input;
+    /// binary variants become `RawData::Bytes`. Non-ASCII bytes are untouched.
+    fn to_ascii_lowercase(&self) -> RawData;
+
+    /// ASCII-uppercase counterpart of [`to_ascii_lowercase`](Self::to_ascii_lowercase).
+    fn to_ascii_uppercase(&self) -> RawData;
+
+    /// `prefix + payload` as `RawData::Bytes`, built in one exactly sized
+    /// buffer.
+    fn with_prefix(&self, prefix: &[u8]) -> RawData;
+
+    /// `payload + suffix` as `RawData::Bytes`, built in one exactly sized
+    /// buffer.
+    fn with_suffix(&self, suffix: &[u8]) -> RawData;
+
+    /// [`with_prefix`](Self::with_prefix) for string headers.
+    fn with_string_prefix(&self, prefix: &str) -> RawData;
+
+    /// [`with_suffix`](Self::with_suffix) for string trailers.
+    fn with_string_suffix(&self, suffix: &str) -> RawData;
+
+    /// Copy of the payload as `RawData::Bytes`, circularly shifted left by
+    /// `n % len()` bytes. Empty payloads are returned empty.
+    fn rotate_left(&self, n: usize) -> RawData;
+
+    /// Right-shifting counterpart of [`rotate_left`](Self::rotate_left).
+    fn rotate_right(&self, n: usize) -> RawData;
+
+    /// Copy of the payload as `RawData::Bytes` with the byte order reversed.
+    fn reverse(&self) -> RawData;
+
+    /// Copy of the payload as `RawData::Bytes` with every run of `byte`
+    /// collapsed to a single occurrence, e.g. NUL padding or repeated
+    /// separators. Runs of any other byte are kept as they are.
+    fn dedup_consecutive(&self, byte: u8) -> RawData;
+
+    /// Iterate over the lines of the payload, borrowing from it.
+    ///
+    /// Follows `str::lines`: lines end at `\n` with an optional preceding
+    /// `\r` stripped, and a trailing line break does not produce a final
+    /// empty line.
+    fn lines(&self) -> impl Iterator<Item = &[u8]> + '_;
+
+    /// Owned variant of [`lines`](Self::lines) yielding one `RawData` per line.
+    ///
+    /// `ArcBytes` and `Bytes` lines are zero-copy `RawData::Bytes` views that
+    /// share the original buffer; `String` lines are copied.
+    fn into_lines(self) -> impl Iterator<Item = RawData>
+    where
+        Self: Sized;
+
+    /// Split the payload at every `delim`, treating it as a separator:
+    /// `"a,b,"` yields `"a"`, `"b"`, `""` and empty input yields one empty
+    /// element, like `slice::split`.
+    fn split(&self, delim: u8) -> impl Iterator<Item = &[u8]> + '_;
+
+    /// Split the payload treating `delim` as a terminator: `"a,b,"` and
+    /// `"a,b"` both yield `"a"`, `"b"`, and empty input yields nothing.
+    /// Only the single element after a final `delim` is suppressed.
+    fn split_terminated(&self, delim: u8) -> impl Iterator<Item = &[u8]> + '_;
+
+    /// Convert the payload into an `Arc<[u8]>`, undoing the copy that
+    /// `RawData::from_arc_slice` has to make.
+    ///
+    /// Cost per path:
+    /// - uniquely owned `ArcBytes` and `String`: the buffer is moved out and
+    ///   handed to `Arc::<[u8]>::from(Vec<u8>)`, a single allocation plus
+    ///   `memcpy` with no intermediate clone of the vector;
+    /// - shared `ArcBytes` and `Bytes`: the bytes are copied from the slice.
+    fn into_arc_slice(self) -> Arc<[u8]>
+    where
+        Self: Sized;
+
+    /// The payload as `Cow::Owned`. `String` and uniquely owned `ArcBytes`
+    /// buffers are moved without copying; other payloads are copied.
+    fn into_cow<'a>(self) -> Cow<'a, [u8]>
+    where
+        Self: Sized;
+
+    /// `Cow::Borrowed(self.as_bytes())`, without copying.
+    fn as_cow(&self) -> Cow<'_, [u8]>;
+
+    /// The shared buffer of an `ArcBytes` payload, `None` for other variants.
+    ///
+    /// Cloning the returned `Arc` shares the exact same allocation.
+    fn as_arc_bytes(&self) -> Option<&Arc<Vec<u8>>>;
+
+    /// The payload as a byte slice suitable as `nom` parser input.
+    ///
+    /// Same as `as_bytes()`; `RawData` cannot implement `nom`'s input traits
+    /// itself, so parsers take this slice (or use
+    /// [`parse_nom`](Self::parse_nom) with the `nom` feature).
+    fn nom_input(&self) -> &[u8];
+
+    /// Run a complete-input `nom` parser over the payload, returning the
+    /// unconsumed remainder and the parsed value.
+    ///
+    /// `Incomplete` maps to `NotComplete`; parse errors map to `FormatError`
+    /// naming the `nom` error kind.
+    #[cfg(feature = "nom")]
+    fn parse_nom<'a, O, P>(&'a self, parser: P) -> WparseResult<(&'a [u8], O)>
+    where
+        P: nom::Parser<&'a [u8], Output = O, Error = nom::error::Error<&'a [u8]>>;
+
+    /// Split at the given byte offsets into `offsets.len() + 1` pieces.
+    ///
+    /// Offsets must be strictly increasing and at most `len()`, otherwise
+    /// this fails with `OutOfBounds`. Pieces are always `RawData::Bytes`:
+    /// zero-copy views of a `Bytes`/`ArcBytes` payload, or of a single copy
+    /// of a `String` payload, since offsets need not fall on char boundaries.
+    fn split_at_offsets(&self, offsets: &[usize]) -> WparseResult<Vec<RawData>>;
+
+    /// Split a fixed-width record into one piece per entry of `field_sizes`.
+    ///
+    /// The sizes must add up to exactly `len()`, otherwise this fails with
+    /// `OutOfBounds`. Zero-width fields yield empty pieces. Pieces are
+    /// `RawData::Bytes`, as in [`split_at_offsets`](Self::split_at_offsets).
+    fn split_fixed_fields(&self, field_sizes: &[usize]) -> WparseResult<Vec<RawData>>;
+
+    /// Byte offset of the `char_pos`-th Unicode scalar value; `len()` when
+    /// `char_pos` equals the char count.
+    ///
+    /// Fails with `WparseReason::Encoding` if the payload is not valid UTF-8
+    /// and with `OutOfBounds` if it has fewer than `char_pos` chars.
+    fn char_offset_to_byte_offset(&self, char_pos: usize) -> WparseResult<usize>;
+
+    /// Split after the first `char_pos` chars, never inside a multi-byte
+    /// sequence. Both halves are copied into `RawData::String`; errors as
+    /// for [`char_offset_to_byte_offset`](Self::char_offset_to_byte_offset).
+    fn split_at_char_boundary(&self, char_pos: usize) -> WparseResult<(RawData, RawData)>;
+
+    /// Split into `(prefix, rest)` where `prefix` is the longest run of
+    /// leading bytes satisfying `predicate`.
+    ///
+    /// Pieces of `Bytes`/`ArcBytes` payloads are zero-copy `RawData::Bytes`
+    /// views; `String` pieces stay strings when the split falls on a char
+    /// boundary.
+    fn take_while<F: Fn(u8) -> bool>(&self, predicate: F) -> (RawData, RawData);
+
+    /// The `rest` half of [`take_while`](Self::take_while).
+    fn skip_while<F: Fn(u8) -> bool>(&self, predicate: F) -> RawData;
+
+    /// First byte, `None` when empty.
+    fn first(&self) -> Option<u8>;
+
+    /// Last byte, `None` when empty.
+    fn last(&self) -> Option<u8>;
+
+    /// Byte at `index`, `None` when out of range.
+    fn get(&self, index: usize) -> Option<u8>;
+
+    /// The first `n` bytes, or the whole payload if it is shorter.
+    ///
+    /// Zero-copy for `Bytes`/`ArcBytes`; a `String` stays a `String` unless the
+    /// cut falls inside a UTF-8 sequence.
+    fn first_n(&self, n: usize) -> RawData;
+
+    /// Length of the longest common byte prefix of `self` and `other`.
+    fn common_prefix_len(&self, other: &RawData) -> usize;
+
+    /// Prefix-delta encode the payload against `base`: the length of their
+    /// common prefix and the remaining suffix of `self`, a zero-copy slice
+    /// for `Bytes`/`ArcBytes`. [`apply_delta`](Self::apply_delta) inverts it.
+    fn delta_from(&self, base: &RawData) -> (usize, RawData);
+
+    /// Rebuild a payload from the first `shared` bytes of `base` followed by
+    /// `suffix`, as `RawData::Bytes`.
+    ///
+    /// # Panics
+    /// If `shared > base.len()`.
+    fn apply_delta(base: &RawData, shared: usize, suffix: &RawData) -> RawData
+    where
+        Self: Sized;
+
+    /// Whether the payload bytes equal `other`, whatever the variant; takes
+    /// `Bytes`, `Vec<u8>` or any other byte slice via deref.
+    fn content_eq(&self, other: &[u8]) -> bool;
+
+    /// Content equality between two payloads, ignoring their variants.
+    ///
+    /// Two `ArcBytes` sharing the same allocation compare equal without a
+    /// byte scan.
+    fn raw_eq(&self, other: &RawData) -> bool;
+
+    /// Lexicographic byte comparison with `other`, ignoring the variant.
+    ///
+    /// Usable as a sort key: `items.sort_by(|a, b| a.cmp_bytes(b.as_bytes()))`.
+    fn cmp_bytes(&self, other: &[u8]) -> std::cmp::Ordering;
+
+    /// Whether every byte equals `byte`, e.g. to validate fill padding.
+    /// Vacuously `true` for an empty payload.
+    fn contains_only(&self, byte: u8) -> bool;
+
+    /// Whether the payload is all NUL bytes; `contains_only(0)`.
+    fn is_zero_bytes(&self) -> bool;
+
+    /// Whether any byte of the payload occurs in `bytes` (reject-list scan).
+    fn contains_any_of(&self, bytes: &[u8]) -> bool;
+
+    /// Number of occurrences of `byte`, e.g. to pre-size a split.
+    fn count(&self, byte: u8) -> usize;
+
+    /// Number of positions at which `needle` occurs, overlapping matches
+    /// included (`"aaa"` contains `"aa"` twice). An empty needle counts 0.
+    fn count_slice(&self, needle: &[u8]) -> usize;
+
+    /// Offsets of all non-overlapping occurrences of `needle`, scanning
+    /// forward (`"aaa"` contains `"aa"` at `[0]`). An empty needle yields
+    /// no offsets.
+    fn find_all(&self, needle: &[u8]) -> Vec<usize>;
+
+    /// Like [`find_all`](Self::find_all), but scanning backward from the
+    /// end, so offsets come last-first (`"aaa"` gives `[1]` for `"aa"`).
+    fn rfind_all(&self, needle: &[u8]) -> Vec<usize>;
+
+    /// `find_all(needle).len()` without collecting the offsets.
+    fn count_non_overlapping(&self, needle: &[u8]) -> usize;
+
+    /// Shannon entropy of the byte distribution in bits per byte, in
+    /// `[0.0, 8.0]`; `0.0` for empty input.
+    fn entropy(&self) -> f64;
+
+    /// Whether the payload looks compressed or encrypted, i.e.
+    /// `entropy() > 7.0`. Short payloads rarely reach that threshold.
+    fn is_likely_compressed(&self) -> bool;
+
+    /// CRC-32 (IEEE) of the payload.
+    fn crc32(&self) -> u32;
+
+    /// Adler-32 of the payload.
+    fn adler32(&self) -> u32;
+
+    /// Digest of the payload using `kind`, see [`Checksum::digest`].
+    fn checksum(&self, kind: Checksum) -> Vec<u8>;
+
+    /// SHA-256 digest of the payload.
+    #[cfg(feature = "crypto")]
+    fn sha256_hash(&self) -> [u8; 32];
+
+    /// [`sha256_hash`](Self::sha256_hash) as lowercase hex.
+    #[cfg(feature = "crypto")]
+    fn sha256_hex(&self) -> String;
+
+    /// BLAKE3 digest of the payload.
+    #[cfg(feature = "crypto")]
+    fn blake3_hash(&self) -> [u8; 32];
+
+    /// Check the payload against an expected SHA-256 digest, failing with
+    /// `WparseReason::ChecksumMismatch("sha256")` whose detail shows both
+    /// digests.
+    #[cfg(feature = "crypto")]
+    fn verify_sha256(&self, expected: &[u8; 32]) -> WparseResult<()>;
+
+    /// Deterministic UUIDv5 of the payload bytes within `namespace`.
+    ///
+    /// Equal bytes give the same id regardless of variant; the entire
+    /// buffer is hashed (SHA-1), so cost grows with payload size.
+    #[cfg(feature = "uuid")]
+    fn content_id(&self, namespace: uuid::Uuid) -> uuid::Uuid;
+
+    /// Re-encode UTF-8 text as UTF-16LE bytes (`RawData::Bytes`), as used by
+    /// SMB, LDAP and Windows Event Log.
+    ///
+    /// Non-UTF-8 input fails with `WparseReason::Encoding`.
+    fn encode_utf16_le(&self) -> WparseResult<RawData>;
+
+    /// Big-endian counterpart of [`encode_utf16_le`](Self::encode_utf16_le).
+    fn encode_utf16_be(&self) -> WparseResult<RawData>;
+
+    /// Decode UTF-16LE bytes into `RawData::String`.
+    ///
+    /// Odd-length input and unpaired surrogates fail with
+    /// `WparseReason::Encoding`.
+    fn decode_utf16_le(&self) -> WparseResult<RawData>;
+
+    /// Big-endian counterpart of [`decode_utf16_le`](Self::decode_utf16_le).
+    fn decode_utf16_be(&self) -> WparseResult<RawData>;
+
+    /// Byte-level edit script turning `self` into `other`, see [`RawDataDiff`].
+    ///
+    /// The script is a shortest one while the payloads differ by at most
+    /// about a thousand edits; beyond that, the part between their common
+    /// prefix and suffix is reported as one `Delete` and one `Insert`.
+    fn diff(&self, other: &RawData) -> Vec<RawDataDiff>;
+
+    /// Line-per-run `=`/`-`/`+` rendering of [`diff`](Self::diff), meant for
+    /// assertion failure messages.
+    fn diff_display(&self, other: &RawData) -> String;
+
+    /// `xxd`-style dump: offset, 16 bytes of hex in 2-byte groups, and an
+    /// ASCII gutter with `.` for non-printable bytes.
+    fn hexdump(&self) -> String;
+
+    /// Formatting wrapper whose `Display` and `{:#?}` render [`hexdump`](Self::hexdump)
+    /// output without building an intermediate `String`.
+    fn hex_view(&self) -> HexDump<'_>;
+
+    /// Standard padded base64 wrapped at `line_len` characters, as used by
+    /// PEM (64) and MIME (76). Lines are separated by `\n` with no trailing
+    /// line break.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `line_len` is zero.
+    fn encode_base64_chunked(&self, line_len: usize) -> String;
+
+    /// Decode base64 that may be wrapped over several lines; all ASCII
+    /// whitespace is ignored. Malformed input fails with
+    /// `WparseReason::Encoding`.
+    fn decode_base64_multiline(s: &str) -> WparseResult<RawData>
+    where
+        Self: Sized;
+
+    /// Read environment variable `name` into `RawData::String`.
+    ///
+    /// An unset variable fails with
+    /// `WparseReason::Plugin("variable not found: NAME")`; a value that is
+    /// not valid Unicode fails with `WparseReason::Encoding`.
+    fn from_env_var(name: &str) -> WparseResult<RawData>
+    where
+        Self: Sized;
+
+    /// Read environment variable `name` and base64-decode it into
+    /// `RawData::Bytes`, as in [`decode_base64_multiline`](Self::decode_base64_multiline).
+    fn from_env_var_base64(name: &str) -> WparseResult<RawData>
+    where
+        Self: Sized;
+
+    /// Percent-encode every byte outside the RFC 3986 unreserved set
+    /// (`ALPHA / DIGIT / - . _ ~`), for use as a URI path or query component.
+    fn to_uri_component(&self) -> String;
+
+    /// Decode a percent-encoded URI component; `+` is kept literally.
+    ///
+    /// The result is `RawData::String` when it is valid UTF-8 and
+    /// `RawData::Bytes` otherwise. A `%` not followed by two hex digits fails
+    /// with `WparseReason::Encoding`.
+    fn from_uri_component(s: &str) -> WparseResult<RawData>
+    where
+        Self: Sized;
+
+    /// Like [`to_uri_component`](Self::to_uri_component), but encoding spaces
+    /// as `+` (`application/x-www-form-urlencoded`).
+    fn to_query_string(&self) -> String;
+
+    /// Inverse of [`to_query_string`](Self::to_query_string): `+` decodes to
+    /// a space, otherwise as [`from_uri_component`](Self::from_uri_component).
+    fn from_query_string(s: &str) -> WparseResult<RawData>
+    where
+        Self: Sized;
+
+    /// gzip-compress the payload into `RawData::Bytes`.
+    #[cfg(feature = "compression")]
+    fn compress_gzip(&self) -> WparseResult<RawData>;
+
+    /// Decompress a gzip payload; malformed input fails with
+    /// `WparseReason::Encoding("gzip")`.
+    #[cfg(feature = "compression")]
+    fn decompress_gzip(&self) -> WparseResult<RawData>;
+
+    /// zlib-compress the payload into `RawData::Bytes`.
+    #[cfg(feature = "compression")]
+    fn compress_zlib(&self) -> WparseResult<RawData>;
+
+    /// Decompress a zlib payload; malformed input fails with
+    /// `WparseReason::Encoding("zlib")`.
+    #[cfg(feature = "compression")]
+    fn decompress_zlib(&self) -> WparseResult<RawData>;
+}
+
+impl RawDataExt for RawData {
+    fn from_arc_bytes_with_offset(
+        arc: Arc<Vec<u8>>,
+        offset: usize,
+        len: usize,
+    ) -> WparseResult<RawData> {
+        let end = offset
+            .checked_add(len)
+            .filter(|&end| end <= arc.len())
+            .ok_or_else(|| {
+                DataErrKind::OutOfBounds(format!(
+                    "range {}+{} beyond length {}",
+                    offset,
+                    len,
+                    arc.len()
+                ))
+            })?;
+        Ok(RawData::Bytes(
+            Bytes::from_owner(SharedVec(arc)).slice(offset..end),
+        ))
+    }
+
+    fn from_lines(lines: &[&str]) -> RawData {
+        RawData::String(lines.join("\n"))
+    }
+
+    fn from_lines_crlf(lines: &[&str]) -> RawData {
+        RawData::String(lines.join("\r\n"))
+    }
+
+    fn from_lines_bytes(lines: &[&[u8]]) -> RawData {
+        RawData::Bytes(Bytes::from(lines.join(&b'\n')))
+    }
+
+    fn from_lines_iter<I>(iter: I) -> RawData
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut text = String::new();
+        for (i, line) in iter.into_iter().enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(line.as_ref());
+        }
+        RawData::String(text)
+    }
+
+    fn writer() -> RawDataWriter {
+        RawDataWriter::new()
+    }
+
+    fn char_count(&self) -> Option<usize> {
+        as_text(self).map(|s| s.chars().count())
+    }
+
+    fn truncate_to_chars(&self, n: usize) -> Option<RawData> {
+        let text = as_text(self)?;
+        let end = text.char_indices().nth(n).map_or(text.len(), |(i, _)| i);
+        Some(RawData::from_string(&text[..end]))
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            RawData::String(s) => s.capacity(),
+            RawData::Bytes(b) => b.len(),
+            RawData::ArcBytes(arc) => arc.capacity(),
+        }
+    }
+
+    fn shrink_to_fit(&mut self) {
+        match self {
+            RawData::String(s) => s.shrink_to_fit(),
+            RawData::Bytes(_) => {}
+            RawData::ArcBytes(arc) => {
+                if let Some(vec) = Arc::get_mut(arc) {
+                    vec.shrink_to_fit();
+                }
+            }
+        }
+    }
+
+    fn replace_first(&self, find: &[u8], replace: &[u8]) -> RawData {
+        replace_bytes(self.as_bytes(), find, replace, 1)
+    }
+
+    fn replace_all(&self, find: &[u8], replace: &[u8]) -> RawData {
+        replace_bytes(self.as_bytes(), find, replace, usize::MAX)
+    }
+
+    fn to_uppercase_string(&self) -> Option<String> {
+        as_text(self).map(str::to_uppercase)
+    }
+
+    fn to_lowercase_string(&self) -> Option<String> {
+        as_text(self).map(str::to_lowercase)
+    }
+
+    fn ascii_uppercase(&self) -> RawData {
+        RawData::Bytes(Bytes::from(self.as_bytes().to_ascii_uppercase()))
+    }
+
+    fn to_ascii_lowercase(&self) -> RawData {
+        match self {
+            RawData::String(s) => RawData::String(s.to_ascii_lowercase()),
+            other => RawData::Bytes(Bytes::from(other.as_bytes().to_ascii_lowercase())),
+        }
+    }
+
+    fn to_ascii_uppercase(&self) -> RawData {
+        match self {
+            RawData::String(s) => RawData::String(s.to_ascii_uppercase()),
+            other => RawData::Bytes(Bytes::from(other.as_bytes().to_ascii_uppercase())),
+        }
+    }
+
+    fn with_prefix(&self, prefix: &[u8]) -> RawData {
+        concat(&[prefix, self.as_bytes()])
+    }
+
+    fn with_suffix(&self, suffix: &[u8]) -> RawData {
+        concat(&[self.as_bytes(), suffix])
+    }
+
+    fn with_string_prefix(&self, prefix: &str) -> RawData {
+        self.with_prefix(prefix.as_bytes())
+    }
+
+    fn with_string_suffix(&self, suffix: &str) -> RawData {
+        self.with_suffix(suffix.as_bytes())
+    }
+
+    fn rotate_left(&self, n: usize) -> RawData {
+        let mut bytes = self.as_bytes().to_vec();
+        if !bytes.is_empty() {
+            let n = n % bytes.len();
+            bytes.rotate_left(n);
+        }
+        RawData::Bytes(Bytes::from(bytes))
+    }
+
+    fn rotate_right(&self, n: usize) -> RawData {
+        let mut bytes = self.as_bytes().to_vec();
+        if !bytes.is_empty() {
+            let n = n % bytes.len();
+            bytes.rotate_right(n);
+        }
+        RawData::Bytes(Bytes::from(bytes))
+    }
+
+    fn reverse(&self) -> RawData {
+        let mut bytes = self.as_bytes().to_vec();
+        bytes.reverse();
+        RawData::Bytes(Bytes::from(bytes))
+    }
+
+    fn dedup_consecutive(&self, byte: u8) -> RawData {
+        let mut bytes = self.as_bytes().to_vec();
+        bytes.dedup_by(|b, prev| *b == byte && *prev == byte);
+        RawData::Bytes(Bytes::from(bytes))
+    }
+
+    fn lines(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let bytes = self.as_bytes();
+        LineRanges::new(bytes).map(move |range| &bytes[range])
+    }
+
+    fn split(&self, delim: u8) -> impl Iterator<Item = &[u8]> + '_ {
+        self.as_bytes().split(move |&b| b == delim)
+    }
+
+    fn split_terminated(&self, delim: u8) -> impl Iterator<Item = &[u8]> + '_ {
+        let bytes = self.as_bytes();
+        let body = bytes.strip_suffix(&[delim]).unwrap_or(bytes);
+        (!bytes.is_empty())
+            .then_some(body)
+            .into_iter()
+            .flat_map(move |body| body.split(move |&b| b == delim))
+    }
+
+    fn into_lines(self) -> impl Iterator<Item = RawData> {
+        let shared = match self {
+            RawData::ArcBytes(arc) => RawData::Bytes(Bytes::from_owner(SharedVec(arc))),
+            other => other,
+        };
+        let ranges: Vec<Range<usize>> = LineRanges::new(shared.as_bytes()).collect();
+        ranges
+            .into_iter()
+            .map(move |range| shared_slice(&shared, range))
+    }
+
+    fn into_cow<'a>(self) -> Cow<'a, [u8]> {
+        let owned = match self {
+            RawData::String(s) => s.into_bytes(),
+            RawData::Bytes(b) => b.to_vec(),
+            RawData::ArcBytes(arc) => Arc::try_unwrap(arc).unwrap_or_else(|arc| arc.to_vec()),
+        };
+        Cow::Owned(owned)
+    }
+
+    fn as_cow(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+
+    fn as_arc_bytes(&self) -> Option<&Arc<Vec<u8>>> {
+        match self {
+            RawData::ArcBytes(arc) => Some(arc),
+            _ => None,
+        }
+    }
+
+    fn into_arc_slice(self) -> Arc<[u8]> {
+        match self {
+            RawData::ArcBytes(arc) => match Arc::try_unwrap(arc) {
+                Ok(vec) => Arc::from(vec),
+                Err(shared) => Arc::from(shared.as_slice()),
+            },
+            RawData::String(s) => Arc::from(s.into_bytes()),
+            RawData::Bytes(b) => Arc::from(&b[..]),
+        }
+    }
+
+    fn split_at_offsets(&self, offsets: &[usize]) -> WparseResult<Vec<RawData>> {
+        let mut prev = None;
+        for &offset in offsets {
+            if offset > self.len() {
+                return Err(DataErrKind::OutOfBounds(format!(
+                    "offset {} beyond length {}",
+                    offset,
+                    self.len()
+                ))
+                .into());
+            }
+            if prev.is_some_and(|prev| offset <= prev) {
+                return Err(DataErrKind::OutOfBounds(format!(
+                    "offset {} not greater than previous offset",
+                    offset
+                ))
+                .into());
+            }
+            prev = Some(offset);
+        }
+        let starts = std::iter::once(0).chain(offsets.iter().copied());
+        let ends = offsets.iter().copied().chain(std::iter::once(self.len()));
+        Ok(with_bytes(self, |data| {
+            starts
+                .zip(ends)
+                .map(|(start, end)| shared_slice(data, start..end))
+                .collect()
+        }))
+    }
+
+    fn split_fixed_fields(&self, field_sizes: &[usize]) -> WparseResult<Vec<RawData>> {
+        let total = field_sizes
+            .iter()
+            .try_fold(0usize, |acc, &size| acc.checked_add(size));
+        if total != Some(self.len()) {
+            return Err(DataErrKind::OutOfBounds(format!(
+                "field sizes do not add up to length {}",
+                self.len()
+            ))
+            .into());
+        }
+        Ok(with_bytes(self, |data| {
+            let mut start = 0;
+            field_sizes
+                .iter()
+                .map(|&size| {
+                    let piece = shared_slice(data, start..start + size);
+                    start += size;
+                    piece
+                })
+                .collect()
+        }))
+    }
+
+    fn char_offset_to_byte_offset(&self, char_pos: usize) -> WparseResult<usize> {
+        let text = utf8_text(self)?;
+        let mut offsets = text.char_indices().map(|(at, _)| at).chain([text.len()]);
+        offsets.nth(char_pos).ok_or_else(|| {
+            DataErrKind::OutOfBounds(format!(
+                "char offset {} beyond {} chars",
+                char_pos,
+                text.chars().count()
+            ))
+            .into()
+        })
+    }
+
+    fn split_at_char_boundary(&self, char_pos: usize) -> WparseResult<(RawData, RawData)> {
+        let at = self.char_offset_to_byte_offset(char_pos)?;
+        let (head, tail) = utf8_text(self)?.split_at(at);
+        Ok((RawData::from_string(head), RawData::from_string(tail)))
+    }
+
+    fn take_while<F: Fn(u8) -> bool>(&self, predicate: F) -> (RawData, RawData) {
+        let at = prefix_len(self.as_bytes(), predicate);
+        with_shared(self, |data| {
+            (
+                shared_slice(data, 0..at),
+                shared_slice(data, at..data.len()),
+            )
+        })
+    }
+
+    fn skip_while<F: Fn(u8) -> bool>(&self, predicate: F) -> RawData {
+        let at = prefix_len(self.as_bytes(), predicate);
+        shared_slice(self, at..self.len())
+    }
+
+    fn first(&self) -> Option<u8> {
+        self.as_bytes().first().copied()
+    }
+
+    fn last(&self) -> Option<u8> {
+        self.as_bytes().last().copied()
+    }
+
+    fn get(&self, index: usize) -> Option<u8> {
+        self.as_bytes().get(index).copied()
+    }
+
+    fn first_n(&self, n: usize) -> RawData {
+        shared_slice(self, 0..n.min(self.len()))
+    }
+
+    fn contains_only(&self, byte: u8) -> bool {
+        self.as_bytes().iter().all(|&b| b == byte)
+    }
+
+    fn is_zero_bytes(&self) -> bool {
+        self.contains_only(0)
+    }
+
+    fn contains_any_of(&self, bytes: &[u8]) -> bool {
+        self.as_bytes().iter().any(|b| bytes.contains(b))
+    }
+
+    fn count(&self, byte: u8) -> usize {
+        self.as_bytes().iter().filter(|&&b| b == byte).count()
+    }
+
+    fn count_slice(&self, needle: &[u8]) -> usize {
+        if needle.is_empty() {
+            return 0;
+        }
+        self.as_bytes()
+            .windows(needle.len())
+            .filter(|window| *window == needle)
+            .count()
+    }
+
+    fn find_all(&self, needle: &[u8]) -> Vec<usize> {
+        FindIter::new(self.as_bytes(), needle).collect()
+    }
+
+    fn rfind_all(&self, needle: &[u8]) -> Vec<usize> {
+        let bytes = self.as_bytes();
+        let mut found = Vec::new();
+        if needle.is_empty() {
+            return found;
+        }
+        let mut end = bytes.len();
+        while let Some(at) = bytes[..end]
+            .windows(needle.len())
+            .rposition(|window| window == needle)
+        {
+            found.push(at);
+            end = at;
+        }
+        found
+    }
+
+    fn count_non_overlapping(&self, needle: &[u8]) -> usize {
+        FindIter::new(self.as_bytes(), needle).count()
+    }
+
+    fn entropy(&self) -> f64 {
+        let bytes = self.as_bytes();
+        if bytes.is_empty() {
+            return 0.0;
+        }
+        let mut freq = [0u64; 256];
+        for &b in bytes {
+            freq[b as usize] += 1;
+        }
+        let total = bytes.len() as f64;
+        let entropy: f64 = freq
+            .iter()
+            .filter(|&&n| n > 0)
+            .map(|&n| {
+                let p = n as f64 / total;
+                -p * p.log2()
+            })
+            .sum();
+        entropy.clamp(0.0, 8.0)
+    }
+
+    fn is_likely_compressed(&self) -> bool {
+        self.entropy() > 7.0
+    }
+
+    fn content_eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+
+    fn raw_eq(&self, other: &RawData) -> bool {
+        if let (RawData::ArcBytes(a), RawData::ArcBytes(b)) = (self, other)
+            && Arc::ptr_eq(a, b)
+        {
+            return true;
+        }
+        self.as_bytes() == other.as_bytes()
+    }
+
+    fn cmp_bytes(&self, other: &[u8]) -> std::cmp::Ordering {
+        self.as_bytes().cmp(other)
+    }
+
+    fn common_prefix_len(&self, other: &RawData) -> usize {
+        self.as_bytes()
+            .iter()
+            .zip(other.as_bytes())
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    fn delta_from(&self, base: &RawData) -> (usize, RawData) {
+        let shared = self.common_prefix_len(base);
+        (shared, shared_slice(self, shared..self.len()))
+    }
+
+    fn apply_delta(base: &RawData, shared: usize, suffix: &RawData) -> RawData {
+        concat(&[&base.as_bytes()[..shared], suffix.as_bytes()])
+    }
+
+    fn crc32(&self) -> u32 {
+        checksum::crc32(self.as_bytes())
+    }
+
+    fn adler32(&self) -> u32 {
+        checksum::adler32(self.as_bytes())
+    }
+
+    fn checksum(&self, kind: Checksum) -> Vec<u8> {
+        kind.digest(self.as_bytes())
+    }
+
+    #[cfg(feature = "crypto")]
+    fn sha256_hash(&self) -> [u8; 32] {
+        use sha2::Digest;
+        sha2::Sha256::digest(self.as_bytes()).into()
+    }
+
+    #[cfg(feature = "crypto")]
+    fn sha256_hex(&self) -> String {
+        hex_string(&self.sha256_hash())
+    }
+
+    #[cfg(feature = "crypto")]
+    fn blake3_hash(&self) -> [u8; 32] {
+        blake3::hash(self.as_bytes()).into()
+    }
+
+    #[cfg(feature = "crypto")]
+    fn verify_sha256(&self, expected: &[u8; 32]) -> WparseResult<()> {
+        let actual = self.sha256_hash();
+        if &actual == expected {
+            return Ok(());
+        }
+        Err(WparseReason::ChecksumMismatch("sha256".into())
+            .into_traced_err()
+            .with_detail(format!(
+                "expected {}, got {}",
+                hex_string(expected),
+                hex_string(&actual)
+            )))
+    }
+
+    #[cfg(feature = "uuid")]
+    fn content_id(&self, namespace: uuid::Uuid) -> uuid::Uuid {
+        uuid::Uuid::new_v5(&namespace, self.as_bytes())
+    }
+
+    fn encode_utf16_le(&self) -> WparseResult<RawData> {
+        encode_utf16(self, u16::to_le_bytes)
+    }
+
+    fn encode_utf16_be(&self) -> WparseResult<RawData> {
+        encode_utf16(self, u16::to_be_bytes)
+    }
+
+    fn decode_utf16_le(&self) -> WparseResult<RawData> {
+        decode_utf16(self, u16::from_le_bytes)
+    }
+
+    fn decode_utf16_be(&self) -> WparseResult<RawData> {
+        decode_utf16(self, u16::from_be_bytes)
+    }
+
+    fn diff(&self, other: &RawData) -> Vec<RawDataDiff> {
+        crate::diff::diff(self.as_bytes(), other.as_bytes())
+    }
+
+    fn diff_display(&self, other: &RawData) -> String {
+        crate::diff::diff_display(self.as_bytes(), other.as_bytes())
+    }
+
+    fn hexdump(&self) -> String {
+        crate::hexdump::hexdump(self.as_bytes())
+    }
+
+    fn hex_view(&self) -> HexDump<'_> {
+        HexDump(self)
+    }
+
+    fn nom_input(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    #[cfg(feature = "nom")]
+    fn parse_nom<'a, O, P>(&'a self, mut parser: P) -> WparseResult<(&'a [u8], O)>
+    where
+        P: nom::Parser<&'a [u8], Output = O, Error = nom::error::Error<&'a [u8]>>,
+    {
+        parser.parse(self.nom_input()).map_err(|err| match err {
+            nom::Err::Incomplete(_) => DataErrKind::NotComplete.into(),
+            nom::Err::Error(e) | nom::Err::Failure(e) => DataErrKind::FormatError(
+                format!("nom {:?} at offset {}", e.code, self.len() - e.input.len()),
+                None,
+            )
+            .into(),
+        })
+    }
+
+    fn encode_base64_chunked(&self, line_len: usize) -> String {
+        assert!(line_len > 0, "base64 line length must be non-zero");
+        let encoded = crate::base64::encode(self.as_bytes());
+        let mut out = String::with_capacity(encoded.len() + encoded.len() / line_len);
+        for (i, line) in encoded.as_bytes().chunks(line_len).enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            // The base64 alphabet is ASCII, so every chunk is valid UTF-8.
+            out.push_str(std::str::from_utf8(line).unwrap_or_default());
+        }
+        out
+    }
+
+    fn decode_base64_multiline(s: &str) -> WparseResult<RawData> {
+        let compact: Vec<u8> = s.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+        crate::base64::decode(&compact)
+            .map(|v| RawData::Bytes(Bytes::from(v)))
+            .map_err(|reason| {
+                WparseReason::Encoding(format!("base64: {}", reason)).into_traced_err()
+            })
+    }
+
+    fn from_env_var(name: &str) -> WparseResult<RawData> {
+        match std::env::var(name) {
+            Ok(value) => Ok(RawData::String(value)),
+            Err(std::env::VarError::NotPresent) => {
+                Err(WparseReason::Plugin(format!("variable not found: {}", name)).into_traced_err())
+            }
+            Err(std::env::VarError::NotUnicode(_)) => Err(WparseReason::Encoding(format!(
+                "env: variable {} is not valid Unicode",
+                name
+            ))
+            .into_traced_err()),
+        }
+    }
+
+    fn from_env_var_base64(name: &str) -> WparseResult<RawData> {
+        let value = RawData::from_env_var(name)?;
+        RawData::decode_base64_multiline(as_text(&value).unwrap_or_default())
+    }
+
+    fn to_uri_component(&self) -> String {
+        crate::percent::encode(self.as_bytes(), false)
+    }
+
+    fn from_uri_component(s: &str) -> WparseResult<RawData> {
+        percent_decode(s, false)
+    }
+
+    fn to_query_string(&self) -> String {
+        crate::percent::encode(self.as_bytes(), true)
+    }
+
+    fn from_query_string(s: &str) -> WparseResult<RawData> {
+        percent_decode(s, true)
+    }
+
+    #[cfg(feature = "compression")]
+    fn compress_gzip(&self) -> WparseResult<RawData> {
+        crate::compression::gzip_encode(self.as_bytes()).map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    #[cfg(feature = "compression")]
+    fn decompress_gzip(&self) -> WparseResult<RawData> {
+        crate::compression::gzip_decode(self.as_bytes()).map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    #[cfg(feature = "compression")]
+    fn compress_zlib(&self) -> WparseResult<RawData> {
+        crate::compression::zlib_encode(self.as_bytes()).map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+
+    #[cfg(feature = "compression")]
+    fn decompress_zlib(&self) -> WparseResult<RawData> {
+        crate::compression::zlib_decode(self.as_bytes()).map(|v| RawData::Bytes(Bytes::from(v)))
+    }
+}
+
+/// Byte ranges of the lines in a buffer, with `str::lines` semantics.
+struct LineRanges<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> LineRanges<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+}
+
+impl Iterator for LineRanges<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.bytes.len() {
+            return None;
+        }
+        let start = self.pos;
+        let end = self.bytes[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(self.bytes.len(), |i| start + i);
+        self.pos = end + 1;
+        let end = if end < self.bytes.len() && end > start && self.bytes[end - 1] == b'\r' {
+            end - 1
+        } else {
+            end
+        };
+        Some(start..end)
+    }
+}
+
+/// The payload as `&str`, validating UTF-8 for the binary variants.
+pub(crate) fn as_text(data: &RawData) -> Option<&str> {
+    match data {
+        RawData::String(s) => Some(s.as_str()),
+        _ => std::str::from_utf8(data.as_bytes()).ok(),
+    }
+}
+
+fn encode_utf16(data: &RawData, to_bytes: fn(u16) -> [u8; 2]) -> WparseResult<RawData> {
+    let text = as_text(data).ok_or_else(|| {
+        WparseReason::Encoding("utf16: input is not valid UTF-8".into()).into_traced_err()
+    })?;
+    let mut out = Vec::with_capacity(text.len() * 2);
+    for unit in text.encode_utf16() {
+        out.extend_from_slice(&to_bytes(unit));
+    }
+    Ok(RawData::Bytes(Bytes::from(out)))
+}
+
+fn decode_utf16(data: &RawData, from_bytes: fn([u8; 2]) -> u16) -> WparseResult<RawData> {
+    let bytes = data.as_bytes();
+    if !bytes.len().is_multiple_of(2) {
+        return Err(
+            WparseReason::Encoding(format!("utf16: odd input length {}", bytes.len()))
+                .into_traced_err(),
+        );
+    }
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map(RawData::String)
+        .map_err(|err| {
+            WparseReason::Encoding(format!(
+                "utf16: unpaired surrogate {:#06x}",
+                err.unpaired_surrogate()
+            ))
+            .into_traced_err()
+        })
+}
+
+/// Concatenate `parts` into a single exactly sized `RawData::Bytes`.
+fn concat(parts: &[&[u8]]) -> RawData {
+    let mut buf = BytesMut::with_capacity(parts.iter().map(|p| p.len()).sum());
+    for part in parts {
+        buf.extend_from_slice(part);
+    }
+    RawData::Bytes(buf.freeze())
+}
+
+fn prefix_len(bytes: &[u8], predicate: impl Fn(u8) -> bool) -> usize {
+    bytes
+        .iter()
+        .position(|&b| !predicate(b))
+        .unwrap_or(bytes.len())
+}
+
+#[cfg(feature = "crypto")]
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn percent_decode(s: &str, plus_space: bool) -> WparseResult<RawData> {
+    let bytes = crate::percent::decode(s, plus_space)
+        .map_err(|reason| WparseReason::Encoding(format!("uri: {}", reason)).into_traced_err())?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(text) => RawData::String(text),
+        Err(err) => RawData::Bytes(Bytes::from(err.into_bytes())),
+    })
+}
+
+fn utf8_text(data: &RawData) -> WparseResult<&str> {
+    as_text(data).ok_or_else(|| {
+        WparseReason::Encoding("payload is not valid UTF-8".into()).into_traced_err()
+    })
+}
+
+/// Forward scan yielding the offsets of non-overlapping `needle` matches.
+struct FindIter<'a> {
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FindIter<'a> {
+    fn new(haystack: &'a [u8], needle: &'a [u8]) -> Self {
+        // An empty needle matches nothing rather than everywhere.
+        let pos = if needle.is_empty() {
+            haystack.len() + 1
+        } else {
+            0
+        };
+        Self {
+            haystack,
+            needle,
+            pos,
+        }
+    }
+}
+
+impl Iterator for FindIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let at = self.pos + find_bytes(self.haystack.get(self.pos..)?, self.needle)?;
+        self.pos = at + self.needle.len();
+        Some(at)
+    }
+}
+
+/// Offset of the first occurrence of `needle` in `haystack`.
+pub(crate) fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn replace_bytes(src: &[u8], find: &[u8], replace: &[u8], limit: usize) -> RawData {
+    if find.is_empty() {
+        return RawData::Bytes(Bytes::copy_from_slice(src));
+    }
+    let mut out = Vec::with_capacity(src.len());
+    let mut rest = src;
+    let mut replaced = 0;
+    while replaced < limit {
+        let Some(pos) = find_bytes(rest, find) else {
+            break;
+        };
+        out.extend_from_slice(&rest[..pos]);
+        out.extend_from_slice(replace);
+        rest = &rest[pos + find.len()..];
+        replaced += 1;
+    }
+    out.extend_from_slice(rest);
+    RawData::Bytes(Bytes::from(out))
+}
+
+/// Owner adapter so an `Arc<Vec<u8>>` can back a `Bytes` without copying.
+struct SharedVec(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedVec {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Sub-range of `data` that shares the underlying buffer where possible.
+///
+/// `Bytes` and `ArcBytes` yield a zero-copy `RawData::Bytes`; `String` stays a
+/// `String` when `range` falls on character boundaries and is copied into
+/// `Bytes` otherwise. `range` must be within `0..data.len()`.
+pub(crate) fn shared_slice(data: &RawData, range: Range<usize>) -> RawData {
+    match data {
+        RawData::String(s) => match s.get(range.clone()) {
+            Some(sub) => RawData::from_string(sub),
+            None => RawData::Bytes(Bytes::copy_from_slice(&s.as_bytes()[range])),
+        },
+        RawData::Bytes(b) => RawData::Bytes(b.slice(range)),
+        RawData::ArcBytes(arc) => {
+            RawData::Bytes(Bytes::from_owner(SharedVec(arc.clone())).slice(range))
+        }
+    }
+}
+
+/// Run `f` on a view of `data` whose slices share one buffer, so producing
+/// many pieces of an `ArcBytes` payload wraps the `Arc` only once.
+pub(crate) fn with_shared<R>(data: &RawData, f: impl FnOnce(&RawData) -> R) -> R {
+    match data {
+        RawData::ArcBytes(arc) => f(&RawData::Bytes(Bytes::from_owner(SharedVec(arc.clone())))),
+        other => f(other),
+    }
+}
+
+/// Like [`with_shared`], but a `String` payload is first copied into one
+/// `Bytes` buffer, so every slice taken from the view is `RawData::Bytes`.
+fn with_bytes<R>(data: &RawData, f: impl FnOnce(&RawData) -> R) -> R {
+    match data {
+        RawData::String(s) => f(&RawData::Bytes(Bytes::copy_from_slice(s.as_bytes()))),
+        other => with_shared(other, f),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cow_views() {
+        let raw = RawData::Bytes(Bytes::from_static(b"abc"));
+        match raw.as_cow() {
+            Cow::Borrowed(b) => assert_eq!(b.as_ptr(), raw.as_bytes().as_ptr()),
+            Cow::Owned(_) => panic!("as_cow must borrow"),
+        }
+
+        let arc = Arc::new(b"xyz".to_vec());
+        let ptr = arc.as_ptr();
+        match RawData::ArcBytes(arc).into_cow() {
+            Cow::Owned(v) => assert_eq!((v.as_slice(), v.as_ptr()), (&b"xyz"[..], ptr)),
+            Cow::Borrowed(_) => panic!("into_cow must own"),
+        }
+        assert_eq!(&*RawData::from_string("s").into_cow(), b"s");
+        assert_eq!(&*raw.into_cow(), b"abc");
+    }
+
+    #[test]
+    fn test_as_arc_bytes_only_for_arc_variant() {
+        let arc = Arc::new(b"abc".to_vec());
+        let raw = RawData::ArcBytes(arc.clone());
+        assert!(Arc::ptr_eq(raw.as_arc_bytes().unwrap(), &arc));
+        assert!(RawData::from_string("abc").as_arc_bytes().is_none());
+        assert!(
+            RawData::Bytes(Bytes::from_static(b"abc"))
+                .as_arc_bytes()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_into_arc_slice() {
+        let arc = Arc::new(b"payload".to_vec());
+        let shared = RawData::ArcBytes(arc.clone()).into_arc_slice();
+        assert_eq!(&shared[..], b"payload");
+        assert_eq!(Arc::strong_count(&arc), 1);
+
+        let unique = RawData::ArcBytes(arc).into_arc_slice();
+        assert_eq!(&unique[..], b"payload");
+        assert_eq!(&RawData::from_string("abc").into_arc_slice()[..], b"abc");
+        assert_eq!(
+            &RawData::Bytes(Bytes::from_static(b"xyz")).into_arc_slice()[..],
+            b"xyz"
+        );
+    }
+
+    #[test]
+    fn test_char_count_differs_from_len_for_multibyte() {
+        let text = RawData::from_string("héllo, 世界");
+        assert_eq!(text.char_count(), Some(9));
+        assert_ne!(text.char_count(), Some(text.len()));
+
+        let arc = RawData::from_arc_bytes(Arc::new("世界".as_bytes().to_vec()));
+        assert_eq!(arc.char_count(), Some(2));
+
+        let invalid = RawData::Bytes(Bytes::from_static(&[0xFF, 0xFE]));
+        assert_eq!(invalid.char_count(), None);
+    }
+
+    #[test]
+    fn test_truncate_to_chars() {
+        let text = RawData::Bytes(Bytes::from("世界你好"));
+        let head = text.truncate_to_chars(2).unwrap();
+        assert!(matches!(head, RawData::String(ref s) if s == "世界"));
+
+        let all = text.truncate_to_chars(10).unwrap();
+        assert_eq!(all.as_bytes(), "世界你好".as_bytes());
+
+        let invalid = RawData::Bytes(Bytes::from_static(&[0xC3]));
+        assert!(invalid.truncate_to_chars(1).is_none());
+    }
+
+    #[test]
+    fn test_capacity_and_shrink_to_fit() {
+        let mut text = String::with_capacity(64);
+        text.push_str("abc");
+        let mut raw = RawData::String(text);
+        assert_eq!(raw.capacity(), 64);
+        raw.shrink_to_fit();
+        assert_eq!(raw.capacity(), 3);
+
+        let mut vec = Vec::with_capacity(32);
+        vec.extend_from_slice(b"xy");
+        let mut unique = RawData::from_arc_bytes(Arc::new(vec));
+        unique.shrink_to_fit();
+        assert_eq!(unique.capacity(), 2);
+
+        let mut vec = Vec::with_capacity(32);
+        vec.extend_from_slice(b"xy");
+        let arc = Arc::new(vec);
+        let mut shared = RawData::from_arc_bytes(arc.clone());
+        shared.shrink_to_fit();
+        assert_eq!(shared.capacity(), 32);
+
+        let bytes = RawData::Bytes(Bytes::from_static(b"bin"));
+        assert_eq!(bytes.capacity(), 3);
+    }
+
+    #[test]
+    fn test_replace_first_and_all() {
+        let raw = RawData::from_string("a-b-c");
+        assert_eq!(raw.replace_first(b"-", b"::").as_bytes(), b"a::b-c");
+        assert_eq!(raw.replace_all(b"-", b"::").as_bytes(), b"a::b::c");
+        assert!(matches!(raw.replace_all(b"-", b""), RawData::Bytes(_)));
+
+        assert_eq!(raw.replace_all(b"x", b"y").as_bytes(), b"a-b-c");
+        assert_eq!(raw.replace_all(b"", b"y").as_bytes(), b"a-b-c");
+
+        let overlapping = RawData::from_string("aaaa");
+        assert_eq!(overlapping.replace_all(b"aaa", b"b").as_bytes(), b"ba");
+        assert_eq!(overlapping.replace_all(b"aa", b"b").as_bytes(), b"bb");
+    }
+
+    #[test]
+    fn test_case_conversion() {
+        let text = RawData::from_string("Straße Ünï");
+        assert_eq!(text.to_uppercase_string().as_deref(), Some("STRASSE ÜNÏ"));
+        assert_eq!(text.to_lowercase_string().as_deref(), Some("straße ünï"));
+
+        let binary = RawData::Bytes(Bytes::from_static(b"ab\xFFc"));
+        assert_eq!(binary.to_uppercase_string(), None);
+        assert_eq!(binary.to_lowercase_string(), None);
+        let upper = binary.ascii_uppercase();
+        assert!(matches!(upper, RawData::Bytes(_)));
+        assert_eq!(upper.as_bytes(), b"AB\xFFC");
+    }
+
+    #[test]
+    fn test_to_ascii_case_keeps_variant() {
+        let text = RawData::from_string("Content-Type: Ünï");
+        assert!(
+            matches!(text.to_ascii_lowercase(), RawData::String(s) if s == "content-type: Ünï")
+        );
+        assert!(
+            matches!(text.to_ascii_uppercase(), RawData::String(s) if s == "CONTENT-TYPE: ÜNï")
+        );
+
+        let binary = RawData::from_arc_bytes(Arc::new(b"GeT\xff".to_vec()));
+        let lower = binary.to_ascii_lowercase();
+        assert!(matches!(&lower, RawData::Bytes(b) if &b[..] == b"get\xff"));
+        assert_eq!(binary.to_ascii_uppercase().as_bytes(), b"GET\xff");
+    }
+
+    #[test]
+    fn test_lines_trailing_newline_edge_cases() {
+        let collect = |text: &str| -> Vec<Vec<u8>> {
+            RawData::from_string(text)
+                .lines()
+                .map(<[u8]>::to_vec)
+                .collect()
+        };
+        assert!(collect("").is_empty());
+        assert_eq!(collect("a\r\nb\n"), [b"a".to_vec(), b"b".to_vec()]);
+        assert_eq!(collect("a\n\nb"), [b"a".to_vec(), vec![], b"b".to_vec()]);
+        assert_eq!(collect("\n"), [Vec::<u8>::new()]);
+        assert_eq!(collect("a\rb\r"), [b"a\rb\r".to_vec()]);
+    }
+
+    #[test]
+    fn test_into_lines_shares_arc_buffer() {
+        let arc = Arc::new(b"one\ntwo\r\nthree\n".to_vec());
+        let lines: Vec<RawData> = RawData::from_arc_bytes(arc.clone()).into_lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].as_bytes(), b"two");
+        assert_eq!(lines[2].as_bytes().as_ptr(), arc[9..].as_ptr());
+        assert!(lines.iter().all(|line| matches!(line, RawData::Bytes(_))));
+
+        let lines: Vec<RawData> = RawData::from_string("x\ny").into_lines().collect();
+        assert!(matches!(&lines[1], RawData::String(s) if s == "y"));
+    }
+
+    #[test]
+    fn test_split_at_char_boundary() {
+        let raw = RawData::Bytes(Bytes::from("ab€ü日x".as_bytes().to_vec()));
+        assert_eq!(raw.char_offset_to_byte_offset(0).unwrap(), 0);
+        assert_eq!(raw.char_offset_to_byte_offset(3).unwrap(), 5);
+        assert_eq!(raw.char_offset_to_byte_offset(6).unwrap(), raw.len());
+
+        for pos in 0..=6 {
+            let (head, tail) = raw.split_at_char_boundary(pos).unwrap();
+            let (RawData::String(head), RawData::String(tail)) = (head, tail) else {
+                panic!("halves should be strings");
+            };
+            assert_eq!(head.chars().count(), pos);
+            assert_eq!(head + &tail, "ab€ü日x");
+        }
+
+        let err = raw.split_at_char_boundary(7).unwrap_err();
+        assert!(err.detail().as_deref().unwrap().contains("beyond 6 chars"));
+        let invalid = RawData::Bytes(Bytes::from_static(b"a\xe2\x82"));
+        let err = invalid.char_offset_to_byte_offset(1).unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Encoding(_)));
+    }
+
+    #[test]
+    fn test_split_fixed_width_record() {
+        let arc = Arc::new(b"20261014ERRdisk full".to_vec());
+        let record = RawData::from_arc_bytes(arc.clone());
+
+        let fields = record.split_fixed_fields(&[8, 3, 9]).unwrap();
+        let fields: Vec<&[u8]> = fields.iter().map(RawData::as_bytes).collect();
+        assert_eq!(fields, [&b"20261014"[..], b"ERR", b"disk full"]);
+
+        let pieces = record.split_at_offsets(&[8, 11]).unwrap();
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[2].as_bytes().as_ptr(), arc[11..].as_ptr());
+        assert_eq!(record.split_at_offsets(&[]).unwrap()[0].len(), 20);
+    }
+
+    #[test]
+    fn test_split_offsets_out_of_bounds() {
+        let data = RawData::from_string("abcdef");
+        for offsets in [&[7][..], &[3, 3], &[4, 2]] {
+            let err = data.split_at_offsets(offsets).unwrap_err();
+            assert!(
+                err.detail()
+                    .as_deref()
+                    .unwrap()
+                    .starts_with("out of bounds")
+            );
+        }
+        assert!(data.split_fixed_fields(&[2, 2]).is_err());
+        assert!(data.split_fixed_fields(&[usize::MAX, 7]).is_err());
+        assert_eq!(data.split_at_offsets(&[0, 6]).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_split_string_yields_bytes_pieces() {
+        let data = RawData::from_string("h\u{e9}llo");
+        let pieces = data.split_at_offsets(&[2, 3]).unwrap();
+        assert!(
+            pieces
+                .iter()
+                .all(|piece| matches!(piece, RawData::Bytes(_)))
+        );
+        let pieces: Vec<&[u8]> = pieces.iter().map(RawData::as_bytes).collect();
+        assert_eq!(pieces, [&b"h\xc3"[..], b"\xa9", b"llo"]);
+
+        let fields = data.split_fixed_fields(&[1, 2, 3]).unwrap();
+        assert!(
+            fields
+                .iter()
+                .all(|field| matches!(field, RawData::Bytes(_)))
+        );
+        assert_eq!(fields[1].as_bytes(), "\u{e9}".as_bytes());
+    }
+
+    #[test]
+    fn test_from_arc_bytes_with_offset_shares_buffer() {
+        let arc = Arc::new(b"hdr|rec1|rec2".to_vec());
+        let first = RawData::from_arc_bytes_with_offset(arc.clone(), 4, 4).unwrap();
+        assert_eq!(Arc::strong_count(&arc), 2);
+        let second = RawData::from_arc_bytes_with_offset(arc.clone(), 9, 4).unwrap();
+        assert_eq!(Arc::strong_count(&arc), 3);
+
+        assert_eq!(first.as_bytes(), b"rec1");
+        assert_eq!(second.as_bytes(), b"rec2");
+        assert_eq!(second.as_bytes().as_ptr(), arc[9..].as_ptr());
+        drop(first);
+        assert_eq!(Arc::strong_count(&arc), 2);
+
+        for (offset, len) in [(10, 4), (14, 0), (usize::MAX, 2)] {
+            let err = RawData::from_arc_bytes_with_offset(arc.clone(), offset, len).unwrap_err();
+            assert!(
+                err.detail()
+                    .as_deref()
+                    .unwrap()
+                    .starts_with("out of bounds")
+            );
+        }
+        assert!(
+            RawData::from_arc_bytes_with_offset(arc, 13, 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_from_lines_helpers() {
+        assert_eq!(RawData::from_lines(&["a", "b"]).as_bytes(), b"a\nb");
+        assert_eq!(RawData::from_lines_crlf(&["a", "b"]).as_bytes(), b"a\r\nb");
+        assert!(RawData::from_lines(&[]).is_empty());
+
+        let binary = RawData::from_lines_bytes(&[b"\x00\x01", b"\xff"]);
+        assert!(matches!(&binary, RawData::Bytes(b) if &b[..] == b"\x00\x01\n\xff"));
+
+        let owned = vec!["x".to_string(), "y".to_string(), "z".to_string()];
+        assert_eq!(RawData::from_lines_iter(&owned).as_bytes(), b"x\ny\nz");
+        assert_eq!(RawData::from_lines_iter(["one"]).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_byte_accessors() {
+        let empty = RawData::from_string("");
+        assert_eq!(
+            (empty.first(), empty.last(), empty.get(0)),
+            (None, None, None)
+        );
+
+        let data = RawData::Bytes(Bytes::from_static(b"\x16\x03\x01"));
+        assert_eq!(data.first(), Some(0x16));
+        assert_eq!(data.last(), Some(0x01));
+        assert_eq!(data.get(1), Some(0x03));
+        assert_eq!(data.get(3), None);
+
+        assert_eq!(data.first_n(2).as_bytes(), b"\x16\x03");
+        assert_eq!(data.first_n(10).as_bytes(), data.as_bytes());
+        assert!(data.first_n(0).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_delta_round_trip() {
+        let base = RawData::from_string("2024-05-01T10:00:00 host=a msg=start");
+        let records = [
+            RawData::from_string("2024-05-01T10:00:07 host=a msg=stop"),
+            RawData::Bytes(Bytes::from_static(b"2024-05-01T10:00:00 host=a msg=start")),
+            RawData::from_arc_bytes(Arc::new(b"1999".to_vec())),
+            RawData::from_string(""),
+        ];
+        for record in &records {
+            let (shared, suffix) = record.delta_from(&base);
+            assert_eq!(shared + suffix.len(), record.len());
+            let rebuilt = RawData::apply_delta(&base, shared, &suffix);
+            assert!(rebuilt.raw_eq(record));
+        }
+
+        let (shared, suffix) = records[0].delta_from(&base);
+        assert_eq!(shared, 18);
+        assert_eq!(suffix.as_bytes(), b"7 host=a msg=stop");
+        assert_eq!(records[1].delta_from(&base).0, base.len());
+    }
+
+    #[test]
+    fn test_checksums() {
+        let data = RawData::from_arc_bytes(Arc::new(b"123456789".to_vec()));
+        assert_eq!(data.crc32(), 0xCBF4_3926);
+        assert_eq!(data.checksum(Checksum::Crc32), [0xCB, 0xF4, 0x39, 0x26]);
+        assert_eq!(RawData::from_string("Wikipedia").adler32(), 0x11E6_0398);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_sha256_and_blake3_vectors() {
+        // FIPS 180-2 "abc" and the BLAKE3 reference empty-input vector.
+        let abc = RawData::from_string("abc");
+        assert_eq!(
+            abc.sha256_hex(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(abc.checksum(Checksum::Sha256), abc.sha256_hash());
+        assert_eq!(
+            hex_string(&RawData::from_string("").blake3_hash()),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+
+        let expected = abc.sha256_hash();
+        abc.verify_sha256(&expected).unwrap();
+        let err = RawData::from_string("abd")
+            .verify_sha256(&expected)
+            .unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &WparseReason::ChecksumMismatch("sha256".into())
+        );
+        assert!(
+            err.detail()
+                .as_deref()
+                .unwrap()
+                .starts_with("expected ba7816bf")
+        );
+    }
+
+    #[test]
+    fn test_utf16_round_trip() {
+        let bmp = RawData::from_string("Héllo");
+        let le = bmp.encode_utf16_le().unwrap();
+        assert_eq!(le.as_bytes(), b"H\0\xe9\0l\0l\0o\0");
+        assert!(matches!(le.decode_utf16_le().unwrap(), RawData::String(s) if s == "Héllo"));
+
+        // U+1F600 needs the surrogate pair D83D DE00.
+        let emoji = RawData::from_string("a😀");
+        let be = emoji.encode_utf16_be().unwrap();
+        assert_eq!(be.as_bytes(), b"\0a\xd8\x3d\xde\x00");
+        assert_eq!(be.decode_utf16_be().unwrap().as_bytes(), "a😀".as_bytes());
+        let le = emoji.encode_utf16_le().unwrap();
+        assert_eq!(le.as_bytes(), b"a\0\x3d\xd8\x00\xde");
+        assert_eq!(le.decode_utf16_le().unwrap().as_bytes(), "a😀".as_bytes());
+    }
+
+    #[test]
+    fn test_utf16_errors() {
+        let invalid = RawData::Bytes(Bytes::from_static(b"\xff\xfe"));
+        assert!(invalid.encode_utf16_le().is_err());
+
+        let odd = RawData::Bytes(Bytes::from_static(b"a\0b"));
+        let err = odd.decode_utf16_le().unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Encoding(msg) if msg.contains("odd")));
+
+        let lone = RawData::Bytes(Bytes::from_static(b"\x3d\xd8a\0"));
+        assert!(lone.decode_utf16_le().is_err());
+    }
+
+    #[test]
+    fn test_contains_only_and_any_of() {
+        let raw = |b: &'static [u8]| RawData::Bytes(Bytes::from_static(b));
+        assert!(raw(b"    ").contains_only(b' '));
+        assert!(!raw(b"  x ").contains_only(b' '));
+        assert!(raw(b"").contains_only(b' '));
+
+        assert!(raw(b"\0\0\0").is_zero_bytes());
+        assert!(!raw(b"\0\x01").is_zero_bytes());
+        assert!(raw(b"").is_zero_bytes());
+
+        assert!(raw(b"a;b").contains_any_of(b";|"));
+        assert!(!raw(b"ab").contains_any_of(b";|"));
+        assert!(!raw(b"ab").contains_any_of(b""));
+        assert!(!raw(b"").contains_any_of(b";"));
+    }
+
+    #[test]
+    fn test_count_bytes_and_slices() {
+        let raw = RawData::from_string("a,b,,c\r\nd\r\n");
+        assert_eq!(raw.count(b','), 3);
+        assert_eq!(raw.count(b'x'), 0);
+        assert_eq!(raw.count_slice(b"\r\n"), 2);
+        assert_eq!(raw.count_slice(b""), 0);
+        assert_eq!(RawData::from_string("aaa").count_slice(b"aa"), 2);
+        assert_eq!(RawData::from_string("a").count_slice(b"aa"), 0);
+    }
+
+    #[test]
+    fn test_find_all_non_overlapping() {
+        let raw = RawData::from_string("GET / HTTP/1.1\r\nHost: a\r\n\r\nbody");
+        assert_eq!(raw.find_all(b"\r\n"), [14, 23, 25]);
+        assert_eq!(raw.rfind_all(b"\r\n"), [25, 23, 14]);
+        assert_eq!(raw.count_non_overlapping(b"\r\n"), 3);
+        assert!(raw.find_all(b"\n\n").is_empty());
+        assert!(raw.find_all(b"").is_empty());
+        assert_eq!(raw.count_non_overlapping(b""), 0);
+
+        let runs = RawData::Bytes(Bytes::from_static(b"aaaaa"));
+        assert_eq!(runs.find_all(b"aa"), [0, 2]);
+        assert_eq!(runs.rfind_all(b"aa"), [3, 1]);
+        assert_eq!(runs.count_non_overlapping(b"aa"), 2);
+        assert_eq!(runs.count_slice(b"aa"), 4);
+    }
+
+    #[test]
+    fn test_entropy() {
+        assert_eq!(RawData::from_string("").entropy(), 0.0);
+        let zeros = RawData::from_arc_bytes(Arc::new(vec![0u8; 4096]));
+        assert_eq!(zeros.entropy(), 0.0);
+        assert!(!zeros.is_likely_compressed());
+
+        // Every byte value equally often: the maximum of 8 bits per byte.
+        let uniform: Vec<u8> = (0..16).flat_map(|_| 0..=255u8).collect();
+        let uniform = RawData::Bytes(Bytes::from(uniform));
+        assert!((uniform.entropy() - 8.0).abs() < 1e-9);
+        assert!(uniform.is_likely_compressed());
+
+        let text = RawData::from_string("GET /index.html HTTP/1.1");
+        let entropy = text.entropy();
+        assert!((3.0..5.0).contains(&entropy), "{entropy}");
+        assert!(!text.is_likely_compressed());
+    }
+
+    #[test]
+    fn test_split_vs_split_terminated() {
+        let collect = |it: &mut dyn Iterator<Item = &[u8]>| -> Vec<Vec<u8>> {
+            it.map(<[u8]>::to_vec).collect()
+        };
+        let raw = RawData::from_string("a,b,");
+        assert_eq!(
+            collect(&mut raw.split(b',')),
+            [b"a".to_vec(), b"b".to_vec(), Vec::new()]
+        );
+        assert_eq!(
+            collect(&mut raw.split_terminated(b',')),
+            [b"a".to_vec(), b"b".to_vec()]
+        );
+
+        let unterminated = RawData::Bytes(Bytes::from_static(b"a,,b"));
+        assert_eq!(
+            collect(&mut unterminated.split_terminated(b',')),
+            [b"a".to_vec(), Vec::new(), b"b".to_vec()]
+        );
+
+        let empty = RawData::from_string("");
+        assert_eq!(collect(&mut empty.split(b',')), [Vec::<u8>::new()]);
+        assert_eq!(empty.split_terminated(b',').count(), 0);
+        let lone = RawData::from_string(",");
+        assert_eq!(
+            collect(&mut lone.split_terminated(b',')),
+            [Vec::<u8>::new()]
+        );
+    }
+
+    #[test]
+    fn test_with_prefix_and_suffix() {
+        let payload = b"0123456789";
+        let variants = [
+            RawData::from_string("0123456789"),
+            RawData::Bytes(Bytes::from_static(payload)),
+            RawData::from_arc_bytes(Arc::new(payload.to_vec())),
+        ];
+        for raw in &variants {
+            let framed = raw.with_prefix(&[0xde, 0xad, 0xbe, 0xef]);
+            assert!(matches!(framed, RawData::Bytes(_)));
+            assert_eq!(framed.len(), 14);
+            assert_eq!(&framed.as_bytes()[..4], [0xde, 0xad, 0xbe, 0xef]);
+            assert_eq!(&framed.as_bytes()[4..], payload);
+
+            assert_eq!(raw.with_string_prefix("HDR:").as_bytes(), b"HDR:0123456789");
+            assert_eq!(raw.with_suffix(b"\r\n").as_bytes(), b"0123456789\r\n");
+            assert_eq!(raw.with_string_suffix("!").as_bytes(), b"0123456789!");
+        }
+    }
+
+    #[test]
+    fn test_dedup_consecutive() {
+        let raw = RawData::from_string("a,,,b,c,,");
+        let out = raw.dedup_consecutive(b',');
+        assert!(matches!(out, RawData::Bytes(_)));
+        assert_eq!(out.as_bytes(), b"a,b,c,");
+
+        // Only the given byte is collapsed.
+        let padded = RawData::Bytes(Bytes::from_static(b"xx\0\0\0yy\0"));
+        assert_eq!(padded.dedup_consecutive(0).as_bytes(), b"xx\0yy\0");
+        assert!(RawData::from_string("").dedup_consecutive(b',').is_empty());
+    }
+
+    #[test]
+    fn test_rotate_and_reverse() {
+        let raw = RawData::Bytes(Bytes::from_static(&[1, 2, 3]));
+        assert_eq!(raw.rotate_left(1).as_bytes(), [2, 3, 1]);
+        assert_eq!(raw.rotate_right(1).as_bytes(), [3, 1, 2]);
+        assert_eq!(raw.rotate_left(4).as_bytes(), [2, 3, 1]);
+        assert_eq!(
+            raw.rotate_left(2).rotate_right(2).as_bytes(),
+            raw.as_bytes()
+        );
+        assert_eq!(raw.reverse().as_bytes(), [3, 2, 1]);
+
+        let empty = RawData::from_string("");
+        assert!(empty.rotate_left(5).is_empty());
+        assert!(empty.rotate_right(5).is_empty());
+    }
+
+    #[test]
+    fn test_take_while_and_skip_while() {
+        let raw = RawData::from_string("GET /index");
+        let (token, rest) = raw.take_while(|b| b.is_ascii_alphabetic());
+        assert_eq!(token.as_bytes(), b"GET");
+        assert_eq!(rest.as_bytes(), b" /index");
+        assert!(matches!(token, RawData::String(_)));
+
+        let (all, none) = raw.take_while(|_| true);
+        assert_eq!(all.as_bytes(), raw.as_bytes());
+        assert!(none.is_empty());
+        let (none, all) = raw.take_while(|b| b.is_ascii_digit());
+        assert!(none.is_empty());
+        assert_eq!(all.as_bytes(), raw.as_bytes());
+
+        let arc = Arc::new(b"   payload".to_vec());
+        let rest = RawData::ArcBytes(arc.clone()).skip_while(|b| b == b' ');
+        assert_eq!(rest.as_bytes(), b"payload");
+        assert_eq!(rest.as_bytes().as_ptr(), arc[3..].as_ptr());
+    }
+
+    #[test]
+    fn test_content_eq_across_types() {
+        let raw = RawData::from_string("abc");
+        assert!(raw.content_eq(&Bytes::from_static(b"abc")));
+        let vec: Vec<u8> = b"abc".to_vec();
+        assert!(raw.content_eq(&vec));
+        assert!(!raw.content_eq(b"abd"));
+        assert!(raw.raw_eq(&RawData::from_arc_bytes(Arc::new(b"abc".to_vec()))));
+        assert!(!raw.raw_eq(&RawData::from_string("ab")));
+    }
+
+    #[test]
+    fn test_raw_eq_shared_arc() {
+        let arc = Arc::new(b"payload".to_vec());
+        let a = RawData::from_arc_bytes(arc.clone());
+        let b = RawData::from_arc_bytes(arc);
+        assert!(a.raw_eq(&b));
+        assert!(a.raw_eq(&a));
+
+        // Distinct allocations still compare by content.
+        let copy = RawData::from_arc_bytes(Arc::new(b"payload".to_vec()));
+        assert!(a.raw_eq(&copy));
+        let other = RawData::from_arc_bytes(Arc::new(b"payloaD".to_vec()));
+        assert!(!a.raw_eq(&other));
+        assert!(a.raw_eq(&RawData::from_string("payload")));
+    }
+
+    #[test]
+    fn test_cmp_bytes_orders_across_variants() {
+        use std::cmp::Ordering;
+
+        let mut items = vec![
+            RawData::from_string("b"),
+            RawData::Bytes(Bytes::from_static(b"a")),
+            RawData::from_arc_bytes(Arc::new(b"b".to_vec())),
+            RawData::from_string("ab"),
+        ];
+        items.sort_by(|a, b| a.cmp_bytes(b.as_bytes()));
+        items.dedup_by(|a, b| a.cmp_bytes(b.as_bytes()) == Ordering::Equal);
+        let sorted: Vec<&[u8]> = items.iter().map(|r| r.as_bytes()).collect();
+        assert_eq!(sorted, [&b"a"[..], b"ab", b"b"]);
+
+        assert_eq!(items[0].cmp_bytes(b"a"), Ordering::Equal);
+        assert_eq!(items[0].cmp_bytes(b""), Ordering::Greater);
+        let found = items.binary_search_by(|probe| probe.cmp_bytes(b"ab"));
+        assert_eq!(found, Ok(1));
+    }
+
+    #[test]
+    fn test_common_prefix_len() {
+        let raw = |s: &str| RawData::from_string(s);
+        assert_eq!(raw("abc").common_prefix_len(&raw("abc")), 3);
+        assert_eq!(raw("abc").common_prefix_len(&raw("xyz")), 0);
+        assert_eq!(raw("abc").common_prefix_len(&raw("abxy")), 2);
+        assert_eq!(raw("ab").common_prefix_len(&raw("abcd")), 2);
+        assert_eq!(raw("").common_prefix_len(&raw("a")), 0);
+    }
+
+    #[cfg(feature = "nom")]
+    #[test]
+    fn test_parse_nom_tag() {
+        use nom::bytes::complete::tag;
+
+        let data = RawData::from_string("GET /index");
+        let (rest, method) = data.parse_nom(tag(&b"GET "[..])).unwrap();
+        assert_eq!(method, b"GET ");
+        assert_eq!(rest, b"/index");
+
+        let err = data.parse_nom(tag(&b"POST"[..])).unwrap_err();
+        assert!(err.detail().as_deref().unwrap().contains("Tag"));
+    }
+
+    #[test]
+    fn test_base64_chunked_round_trip() {
+        let payload: Vec<u8> = (0..100u8).collect();
+        let raw = RawData::Bytes(Bytes::from(payload.clone()));
+        let pem = raw.encode_base64_chunked(64);
+
+        let lines: Vec<&str> = pem.split('\n').collect();
+        assert_eq!(
+            lines.iter().map(|l| l.len()).collect::<Vec<_>>(),
+            [64, 64, 8]
+        );
+        let decoded = RawData::decode_base64_multiline(&format!("{}\r\n", pem)).unwrap();
+        assert_eq!(decoded.as_bytes(), payload.as_slice());
+
+        let err = RawData::decode_base64_multiline("Zm9v\n!A==").unwrap_err();
+        assert!(matches!(err.reason(), WparseReason::Encoding(msg) if msg.starts_with("base64")));
+    }
+
+    #[test]
+    fn test_uri_component_round_trip() {
+        let raw = RawData::from_string("a b&c=d/é~_.-");
+        let encoded = raw.to_uri_component();
+        assert_eq!(encoded, "a%20b%26c%3Dd%2F%C3%A9~_.-");
+        let decoded = RawData::from_uri_component(&encoded).unwrap();
+        assert!(matches!(&decoded, RawData::String(s) if s == "a b&c=d/é~_.-"));
+
+        let query = raw.to_query_string();
+        assert_eq!(query, "a+b%26c%3Dd%2F%C3%A9~_.-");
+        assert!(RawData::from_query_string(&query).unwrap().raw_eq(&raw));
+        assert_eq!(
+            RawData::from_uri_component("a+b").unwrap().as_bytes(),
+            b"a+b"
+        );
+
+        let binary = RawData::from_uri_component("%FF%00").unwrap();
+        assert!(matches!(&binary, RawData::Bytes(b) if b.as_ref() == b"\xff\x00"));
+        for bad in ["%", "%4", "%zz", "a%2", "%+1"] {
+            let err = RawData::from_uri_component(bad).unwrap_err();
+            assert!(matches!(err.reason(), WparseReason::Encoding(_)), "{bad}");
+        }
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_content_id_is_stable_across_variants() {
+        use uuid::Uuid;
+
+        let text = RawData::from_string("payload");
+        let arc = RawData::from_arc_bytes(Arc::new(b"payload".to_vec()));
+        let id = text.content_id(Uuid::NAMESPACE_OID);
+        assert_eq!(id, arc.content_id(Uuid::NAMESPACE_OID));
+        assert_eq!(id.get_version_num(), 5);
+        assert_ne!(id, text.content_id(Uuid::NAMESPACE_URL));
+        assert_ne!(
+            id,
+            RawData::from_string("other").content_id(Uuid::NAMESPACE_OID)
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_gzip_and_zlib_round_trip() {
+        use crate::WparseReason;
+
+        let raw = RawData::from_string("hello hello hello hello");
+        let gz = raw.compress_gzip().unwrap();
+        assert!(gz.as_bytes().starts_with(b"\x1f\x8b"));
+        assert_eq!(gz.decompress_gzip().unwrap().as_bytes(), raw.as_bytes());
+
+        let z = raw.compress_zlib().unwrap();
+        assert_eq!(z.as_bytes()[0], 0x78);
+        assert_eq!(z.decompress_zlib().unwrap().as_bytes(), raw.as_bytes());
+
+        let err = raw.decompress_gzip().unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::Encoding("gzip".into()));
+    }
+}
@@ -0,0 +1,101 @@
+//! `std::io::Write` / `fmt::Write` sink that collects into [`RawData`].
+
+use std::{fmt, io};
+
+use bytes::BytesMut;
+use wp_model_core::raw::RawData;
+
+/// Growable `io::Write` and `fmt::Write` target that finalizes into
+/// `RawData::Bytes`.
+///
+/// Lets serializers and `write!` calls that expect `impl Write` produce a
+/// `RawData` directly. Writes never fail and `flush` is a no-op. Also
+/// available as `RawData::writer()` through [`RawDataExt`](crate::RawDataExt).
+#[derive(Debug, Clone, Default)]
+pub struct RawDataWriter {
+    buf: BytesMut,
+}
+
+impl RawDataWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: BytesMut::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Finish writing and freeze the buffer without copying.
+    pub fn into_raw(self) -> RawData {
+        RawData::Bytes(self.buf.freeze())
+    }
+
+    /// Alias of [`into_raw`](Self::into_raw).
+    pub fn into_raw_data(self) -> RawData {
+        self.into_raw()
+    }
+}
+
+impl io::Write for RawDataWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.buf.extend_from_slice(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Write for RawDataWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.buf.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer_collects_writes() {
+        use std::io::Write;
+
+        let mut writer = RawDataWriter::with_capacity(16);
+        assert_eq!(writer.write(b"id=").unwrap(), 3);
+        writer.write_all(b"7;").unwrap();
+        write!(writer, "len={}", 42).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(writer.len(), 11);
+
+        let raw = writer.into_raw();
+        assert!(matches!(&raw, RawData::Bytes(b) if &b[..] == b"id=7;len=42"));
+        assert!(RawDataWriter::new().into_raw().is_empty());
+    }
+
+    #[test]
+    fn test_writer_supports_fmt_write() {
+        use crate::RawDataExt;
+        use std::fmt::Write as _;
+
+        let mut writer = RawData::writer();
+        write!(writer, "hello {}", 42).unwrap();
+        assert_eq!(writer.into_raw_data().as_bytes(), b"hello 42");
+    }
+}
@@ -0,0 +1,123 @@
+//! Pipelines whose steps can be reconfigured while in use.
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use wp_model_core::raw::RawData;
+
+use crate::{PipeProcessor, WparseReason, WparseResult};
+
+type StepLock = Mutex<Box<dyn PipeProcessor + Send>>;
+
+/// A pipeline owning its steps behind locks, so that
+/// [`set_step_config`](Self::set_step_config) can change parameters of a
+/// live step through a shared reference.
+///
+/// Unlike [`Pipeline`](crate::Pipeline), steps are not shared as
+/// [`PipeHold`](crate::PipeHold) and each `run` locks one step at a time,
+/// so a reconfiguration takes effect from the next step call on. There is
+/// no observer or output limit.
+#[derive(Default)]
+pub struct ReconfigurablePipeline {
+    steps: Vec<StepLock>,
+}
+
+impl ReconfigurablePipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `step` to the end of the pipeline.
+    pub fn with<P: PipeProcessor + Send + 'static>(mut self, step: P) -> Self {
+        self.steps.push(Mutex::new(Box::new(step)));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Run `input` through every step in order, stopping at the first error.
+    pub fn run(&self, input: RawData) -> WparseResult<RawData> {
+        self.steps
+            .iter()
+            .try_fold(input, |data, step| lock(step).process(data))
+    }
+
+    /// Forward to [`PipeProcessor::set_config`] of the step at `index`.
+    ///
+    /// An out-of-range index fails with a core configuration error.
+    pub fn set_step_config(&self, index: usize, key: &str, value: &str) -> WparseResult<()> {
+        let step = self.steps.get(index).ok_or_else(|| {
+            WparseReason::core_conf()
+                .into_traced_err()
+                .with_detail(format!("no step at index {}", index))
+        })?;
+        lock(step).set_config(key, value)
+    }
+
+    /// [`PipeProcessor::get_config`] of the step at `index`, `None` if the
+    /// index is out of range.
+    pub fn get_step_config(&self, index: usize, key: &str) -> Option<String> {
+        self.steps
+            .get(index)
+            .and_then(|step| lock(step).get_config(key))
+    }
+}
+
+fn lock(step: &StepLock) -> MutexGuard<'_, Box<dyn PipeProcessor + Send>> {
+    step.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl fmt::Debug for ReconfigurablePipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = self.steps.iter().map(|step| lock(step).name()).collect();
+        f.debug_struct("ReconfigurablePipeline")
+            .field("steps", &names)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processors::Strip;
+
+    #[test]
+    fn test_reconfigure_live_step() {
+        let pipeline = ReconfigurablePipeline::new().with(Strip::new(1, 1));
+        assert_eq!(
+            pipeline
+                .run(RawData::from_string("<<x>>"))
+                .unwrap()
+                .as_bytes(),
+            b"<x>"
+        );
+
+        pipeline.set_step_config(0, "head", "2").unwrap();
+        pipeline.set_step_config(0, "tail", "2").unwrap();
+        assert_eq!(pipeline.get_step_config(0, "head").as_deref(), Some("2"));
+        assert_eq!(
+            pipeline
+                .run(RawData::from_string("<<x>>"))
+                .unwrap()
+                .as_bytes(),
+            b"x"
+        );
+
+        // Unknown keys are ignored; bad values and indices are rejected.
+        pipeline.set_step_config(0, "colour", "red").unwrap();
+        let err = pipeline.set_step_config(0, "head", "two").unwrap_err();
+        assert_eq!(err.reason(), &WparseReason::core_conf());
+        assert!(pipeline.set_step_config(1, "head", "0").is_err());
+        assert_eq!(pipeline.get_step_config(1, "head"), None);
+        assert_eq!(
+            format!("{:?}", pipeline),
+            "ReconfigurablePipeline { steps: [\"strip\"] }"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! `RawData`-typed access to [`DataRecord`] fields and simple extraction
+//! of records from raw payloads.
+
+use wp_model_core::model::{DataField, DataRecord, FieldStorage, Value};
+use wp_model_core::raw::RawData;
+
+use crate::error::DataErrKind;
+use crate::raw_ext::as_text;
+use crate::{WparseError, WparseResult};
+
+/// Extension methods reading and writing [`DataRecord`] fields as [`RawData`].
+///
+/// Field values are read through their textual form: `Chars` fields become
+/// `RawData::String` as-is, any other value type is rendered with its
+/// `Display` implementation.
+pub trait DataRecordRawExt {
+    /// Fetch the first field named `field` as `RawData`.
+    fn get_raw(&self, field: &str) -> Option<RawData>;
+
+    /// Store `value` as a chars field named `field`.
+    ///
+    /// An existing field of that name is replaced in place, otherwise the
+    /// field is appended. Non-UTF-8 payloads are stored lossily.
+    fn set_raw(&mut self, field: &str, value: RawData);
+
+    /// All fields in record order, each converted as in [`get_raw`].
+    ///
+    /// [`get_raw`]: DataRecordRawExt::get_raw
+    fn fields_as_raw(&self) -> Vec<(&str, RawData)>;
+}
+
+impl DataRecordRawExt for DataRecord {
+    fn get_raw(&self, field: &str) -> Option<RawData> {
+        self.get_field(field).map(|f| value_to_raw(f.get_value()))
+    }
+
+    fn set_raw(&mut self, field: &str, value: RawData) {
+        let text = match as_text(&value) {
+            Some(text) => text.to_string(),
+            None => String::from_utf8_lossy(value.as_bytes()).into_owned(),
+        };
+        let storage = FieldStorage::from_owned(DataField::from_chars(field, text));
+        match self.items.iter().position(|x| x.get_name() == field) {
+            Some(pos) => self.items[pos] = storage,
+            None => self.items.push(storage),
+        }
+    }
+
+    fn fields_as_raw(&self) -> Vec<(&str, RawData)> {
+        self.field_refs()
+            .map(|f| (f.get_name(), value_to_raw(f.get_value())))
+            .collect()
+    }
+}
+
+/// Parse `k1=v1;k2=v2`-style text into a [`DataRecord`] of chars fields,
+/// with `pair_sep` between pairs and `kv_sep` between key and value.
+///
+/// Pairs are split at the first `kv_sep`, so values may contain it. Empty
+/// pairs (as left by a trailing `pair_sep`) are skipped; a pair without
+/// `kv_sep`, an empty key or non-UTF-8 text fails with `FormatError`.
+pub fn parse_kv(data: &RawData, pair_sep: u8, kv_sep: u8) -> WparseResult<DataRecord> {
+    let mut record = DataRecord::default();
+    for pair in data.as_bytes().split(|&b| b == pair_sep) {
+        if pair.is_empty() {
+            continue;
+        }
+        let Some(at) = pair.iter().position(|&b| b == kv_sep) else {
+            return Err(kv_error("missing key/value separator", pair));
+        };
+        let (key, value) = (&pair[..at], &pair[at + 1..]);
+        if key.is_empty() {
+            return Err(kv_error("empty key", pair));
+        }
+        match (std::str::from_utf8(key), std::str::from_utf8(value)) {
+            (Ok(key), Ok(value)) => record.push_owned(DataField::from_chars(key, value)),
+            _ => return Err(kv_error("invalid UTF-8", pair)),
+        }
+    }
+    Ok(record)
+}
+
+fn kv_error(what: &str, pair: &[u8]) -> WparseError {
+    DataErrKind::FormatError(
+        format!("parse_kv: {}", what),
+        Some(String::from_utf8_lossy(pair).into_owned()),
+    )
+    .into()
+}
+
+fn value_to_raw(value: &Value) -> RawData {
+    match value {
+        Value::Chars(s) => RawData::from_string(s.as_str()),
+        other => RawData::from_string(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_raw_string_field() {
+        let mut record = DataRecord::default();
+        record.push_owned(DataField::from_chars("msg", "hello"));
+        record.push_owned(DataField::from_digit("code", 42));
+
+        assert_eq!(record.get_raw("msg").unwrap().as_bytes(), b"hello");
+        assert_eq!(record.get_raw("code").unwrap().as_bytes(), b"42");
+        assert!(record.get_raw("missing").is_none());
+    }
+
+    #[test]
+    fn test_parse_kv() {
+        let data = RawData::from_string("host=web-1;path=/a=b;;code=200;");
+        let record = parse_kv(&data, b';', b'=').unwrap();
+        assert_eq!(record.len(), 3);
+        assert_eq!(record.get_raw("host").unwrap().as_bytes(), b"web-1");
+        assert_eq!(record.get_raw("path").unwrap().as_bytes(), b"/a=b");
+        assert_eq!(record.get_raw("code").unwrap().as_bytes(), b"200");
+
+        for bad in ["a=1;oops", "=1"] {
+            let err = parse_kv(&RawData::from_string(bad), b';', b'=').unwrap_err();
+            assert_eq!(err.reason(), &crate::WparseReason::data_error());
+            assert!(
+                err.detail()
+                    .as_deref()
+                    .unwrap()
+                    .starts_with("format error : parse_kv")
+            );
+        }
+        let invalid = RawData::Bytes(bytes::Bytes::from_static(b"k=\xff"));
+        assert!(parse_kv(&invalid, b';', b'=').is_err());
+    }
+
+    #[test]
+    fn test_set_raw_replaces_in_place() {
+        let mut record = DataRecord::default();
+        record.push_owned(DataField::from_chars("a", "1"));
+        record.push_owned(DataField::from_chars("b", "2"));
+
+        record.set_raw("a", RawData::from_string("one"));
+        record.set_raw("c", RawData::Bytes(bytes::Bytes::from_static(b"three")));
+
+        let fields: Vec<(&str, Vec<u8>)> = record
+            .fields_as_raw()
+            .into_iter()
+            .map(|(name, raw)| (name, raw.as_bytes().to_vec()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("a", b"one".to_vec()),
+                ("b", b"2".to_vec()),
+                ("c", b"three".to_vec()),
+            ]
+        );
+    }
+}
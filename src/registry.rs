@@ -0,0 +1,259 @@
+//! Name-based construction of pipeline processors from plugin configuration,
+//! and lookup of whole pipelines by name.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use wp_model_core::raw::RawData;
+
+use crate::{PipeHold, Pipeline, WparseReason, WparseResult};
+
+/// Factory that turns string key/value configuration into a [`PipeHold`].
+///
+/// A configuration entry such as `{"processor": "fixed_trim", "length": 4}`
+/// is resolved by looking up the builder whose [`processor_name`] matches
+/// `"fixed_trim"` and handing it the remaining parameters.
+///
+/// [`processor_name`]: ProcessorBuilder::processor_name
+pub trait ProcessorBuilder: Send + Sync {
+    /// Build a processor instance from its configuration parameters.
+    fn build(&self, config: &HashMap<String, String>) -> WparseResult<PipeHold>;
+
+    /// Name under which this builder is registered.
+    fn processor_name(&self) -> &str;
+}
+
+/// Registry of [`ProcessorBuilder`]s keyed by processor name.
+#[derive(Clone, Default)]
+pub struct PipeProcessorRegistry {
+    builders: HashMap<String, Arc<dyn ProcessorBuilder>>,
+}
+
+impl PipeProcessorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a builder, returning the one previously registered under the
+    /// same name, if any.
+    pub fn register<B>(&mut self, builder: B) -> Option<Arc<dyn ProcessorBuilder>>
+    where
+        B: ProcessorBuilder + 'static,
+    {
+        let builder: Arc<dyn ProcessorBuilder> = Arc::new(builder);
+        self.builders
+            .insert(builder.processor_name().to_string(), builder)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.builders.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.builders.keys().map(String::as_str)
+    }
+
+    /// Build the processor registered as `name` using `config`.
+    ///
+    /// Fails with `WparseReason::Plugin` when no builder is registered under
+    /// `name`; configuration errors are reported by the builder itself.
+    pub fn from_config_map(
+        &self,
+        name: &str,
+        config: HashMap<String, String>,
+    ) -> WparseResult<PipeHold> {
+        let builder = self.builders.get(name).ok_or_else(|| {
+            WparseReason::Plugin(format!("unknown processor: {}", name)).into_traced_err()
+        })?;
+        builder.build(&config)
+    }
+}
+
+/// Named [`Pipeline`]s, for routing inputs to the right parser in a service
+/// hosting several of them.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineRegistry {
+    pipelines: HashMap<String, Pipeline>,
+}
+
+impl PipelineRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `pipeline` under `name`, which also becomes its
+    /// [`Pipeline::name`]. Returns the pipeline previously registered under
+    /// that name, if any.
+    pub fn insert(&mut self, name: impl Into<String>, pipeline: Pipeline) -> Option<Pipeline> {
+        let name = name.into();
+        let pipeline = pipeline.with_name(name.clone());
+        self.pipelines.insert(name, pipeline)
+    }
+
+    /// Fails with `WparseReason::Plugin` when no pipeline is registered
+    /// under `name`.
+    pub fn get(&self, name: &str) -> WparseResult<&Pipeline> {
+        self.pipelines.get(name).ok_or_else(|| {
+            WparseReason::Plugin(format!("unknown pipeline: {}", name)).into_traced_err()
+        })
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Pipeline> {
+        self.pipelines.remove(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.pipelines.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.pipelines.keys().map(String::as_str)
+    }
+
+    /// [`Pipeline::run`] the pipeline registered as `name` on `data`.
+    pub fn run(&self, name: &str, data: RawData) -> WparseResult<RawData> {
+        self.get(name)?.run(data)
+    }
+}
+
+/// Fetch a mandatory key from a processor configuration map.
+///
+/// Missing keys are reported as a core configuration error naming the key, so
+/// builders can simply write `required_config(config, "length")?`.
+pub fn required_config<'a>(
+    config: &'a HashMap<String, String>,
+    key: &str,
+) -> WparseResult<&'a str> {
+    config.get(key).map(String::as_str).ok_or_else(|| {
+        WparseReason::core_conf()
+            .into_traced_err()
+            .with_detail(format!("missing config key: {}", key))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PipeProcessor;
+    use crate::processors::Strip;
+
+    struct PrefixStripProcessor {
+        prefix: String,
+    }
+
+    impl PipeProcessor for PrefixStripProcessor {
+        fn process(&self, data: RawData) -> WparseResult<RawData> {
+            let bytes = data.as_bytes();
+            match bytes.strip_prefix(self.prefix.as_bytes()) {
+                Some(rest) => Ok(RawData::Bytes(bytes::Bytes::copy_from_slice(rest))),
+                None => Ok(data),
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "prefix_strip"
+        }
+    }
+
+    struct PrefixStripBuilder;
+
+    impl ProcessorBuilder for PrefixStripBuilder {
+        fn build(&self, config: &HashMap<String, String>) -> WparseResult<PipeHold> {
+            let prefix = required_config(config, "prefix")?;
+            Ok(Arc::new(PrefixStripProcessor {
+                prefix: prefix.to_string(),
+            }))
+        }
+
+        fn processor_name(&self) -> &str {
+            "prefix_strip"
+        }
+    }
+
+    fn registry() -> PipeProcessorRegistry {
+        let mut registry = PipeProcessorRegistry::new();
+        assert!(registry.register(PrefixStripBuilder).is_none());
+        registry
+    }
+
+    #[test]
+    fn test_from_config_map_builds_processor() {
+        let config = HashMap::from([("prefix".to_string(), "hdr:".to_string())]);
+        let proc = registry().from_config_map("prefix_strip", config).unwrap();
+
+        assert_eq!(proc.name(), "prefix_strip");
+        let out = proc.process(RawData::from_string("hdr:payload")).unwrap();
+        assert_eq!(out.as_bytes(), b"payload");
+    }
+
+    #[test]
+    fn test_built_processors_compose_with_pipelines() {
+        let config = HashMap::from([("prefix".to_string(), "hdr:".to_string())]);
+        let build = || registry().from_config_map("prefix_strip", config.clone());
+
+        let tail = Pipeline::new()
+            .with(Arc::new(Strip::new(1, 1)))
+            .with_name("tail");
+        let pipeline = build().unwrap() + tail.clone();
+        assert_eq!((pipeline.len(), pipeline.name()), (2, Some("tail")));
+        let out = pipeline.run(RawData::from_string("hdr:[x]")).unwrap();
+        assert_eq!(out.as_bytes(), b"x");
+
+        let pipeline = tail.with(Arc::new(Strip::new(1, 0))) + build().unwrap();
+        let out = pipeline.run(RawData::from_string("[<hdr:x]")).unwrap();
+        assert_eq!(out.as_bytes(), b"x");
+    }
+
+    #[test]
+    fn test_from_config_map_missing_key() {
+        let err = match registry().from_config_map("prefix_strip", HashMap::new()) {
+            Err(err) => err,
+            Ok(_) => panic!("missing prefix must fail"),
+        };
+        assert_eq!(err.reason(), &WparseReason::core_conf());
+        assert!(err.detail().as_deref().unwrap().contains("prefix"));
+    }
+
+    #[test]
+    fn test_from_config_map_unknown_processor() {
+        let err = match registry().from_config_map("nope", HashMap::new()) {
+            Err(err) => err,
+            Ok(_) => panic!("unknown processor must fail"),
+        };
+        assert_eq!(
+            err.reason(),
+            &WparseReason::Plugin("unknown processor: nope".into())
+        );
+    }
+
+    #[test]
+    fn test_pipeline_registry_routes_by_name() {
+        let mut pipelines = PipelineRegistry::new();
+        assert!(
+            pipelines
+                .insert("brackets", Pipeline::new().with(Arc::new(Strip::new(1, 1))))
+                .is_none()
+        );
+        pipelines.insert("prefix", Pipeline::new().with(Arc::new(Strip::new(4, 0))));
+
+        assert_eq!(pipelines.get("brackets").unwrap().name(), Some("brackets"));
+        let out = pipelines
+            .run("brackets", RawData::from_string("[x]"))
+            .unwrap();
+        assert_eq!(out.as_bytes(), b"x");
+        let out = pipelines
+            .run("prefix", RawData::from_string("hdr:x"))
+            .unwrap();
+        assert_eq!(out.as_bytes(), b"x");
+
+        let err = pipelines
+            .run("csv", RawData::from_string("a,b"))
+            .unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &WparseReason::Plugin("unknown pipeline: csv".into())
+        );
+        assert!(pipelines.remove("prefix").is_some());
+        assert!(!pipelines.contains("prefix"));
+    }
+}
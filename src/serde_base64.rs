@@ -0,0 +1,59 @@
+//! Base64 (de)serialization for [`RawData`] fields.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Envelope {
+//!     #[serde(with = "wp_parse_api::serde_base64")]
+//!     payload: RawData,
+//! }
+//! ```
+//!
+//! Payloads are encoded with the standard alphabet and padding, and always
+//! deserialize into `RawData::Bytes`.
+
+use bytes::Bytes;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serializer};
+use wp_model_core::raw::RawData;
+
+pub fn serialize<S>(data: &RawData, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&crate::base64::encode(data.as_bytes()))
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<RawData, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+    crate::base64::decode(text.as_bytes())
+        .map(|bytes| RawData::Bytes(Bytes::from(bytes)))
+        .map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::{Deserialize, Serialize};
+    use wp_model_core::raw::RawData;
+
+    #[derive(Serialize, Deserialize)]
+    struct Envelope {
+        #[serde(with = "crate::serde_base64")]
+        payload: RawData,
+    }
+
+    #[test]
+    fn test_serde_base64_round_trip() {
+        let env = Envelope {
+            payload: RawData::from_string("hi\u{0}"),
+        };
+        let json = serde_json::to_string(&env).unwrap();
+        assert_eq!(json, r#"{"payload":"aGkA"}"#);
+
+        let back: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.payload.as_bytes(), b"hi\0");
+        assert!(serde_json::from_str::<Envelope>(r#"{"payload":"@@"}"#).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! Helpers for asserting parser output in tests.
+
+use std::path::Path;
+
+use wp_model_core::raw::RawData;
+
+/// Compare the payload bytes of `data` with the contents of the file at `path`.
+///
+/// Intended for golden-file tests: `assert!(raw_eq_file(&out, "fixtures/a.bin")?)`.
+pub fn raw_eq_file(data: &RawData, path: impl AsRef<Path>) -> std::io::Result<bool> {
+    let expected = std::fs::read(path)?;
+    Ok(data.as_bytes() == expected.as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_raw_eq_file() {
+        let path = std::env::temp_dir().join(format!("wp_parse_api_golden_{}", std::process::id()));
+        std::fs::write(&path, b"golden").unwrap();
+
+        assert!(raw_eq_file(&RawData::from_string("golden"), &path).unwrap());
+        assert!(
+            raw_eq_file(
+                &RawData::from_arc_bytes(Arc::new(b"golden".to_vec())),
+                &path
+            )
+            .unwrap()
+        );
+        assert!(!raw_eq_file(&RawData::from_string("other"), &path).unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(raw_eq_file(&RawData::from_string("golden"), &path).is_err());
+    }
+}
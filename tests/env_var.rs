@@ -0,0 +1,25 @@
+//! Kept in its own test binary: `set_var` is only sound while no other
+//! thread touches the environment, and this is the only test in the process.
+
+use wp_model_core::raw::RawData;
+use wp_parse_api::{RawDataExt, WparseReason};
+
+#[test]
+fn test_from_env_var() {
+    // SAFETY: this binary runs a single test, so no other thread reads or
+    // writes the environment while the variables are set.
+    unsafe {
+        std::env::set_var("WP_PARSE_API_TEST_ENV_TEXT", "secret");
+        std::env::set_var("WP_PARSE_API_TEST_ENV_B64", "AAH/");
+    }
+    let text = RawData::from_env_var("WP_PARSE_API_TEST_ENV_TEXT").unwrap();
+    assert!(matches!(&text, RawData::String(s) if s == "secret"));
+    let bin = RawData::from_env_var_base64("WP_PARSE_API_TEST_ENV_B64").unwrap();
+    assert!(matches!(&bin, RawData::Bytes(b) if b.as_ref() == [0x00, 0x01, 0xff]));
+
+    let err = RawData::from_env_var("WP_PARSE_API_TEST_ENV_MISSING").unwrap_err();
+    assert_eq!(
+        err.reason(),
+        &WparseReason::Plugin("variable not found: WP_PARSE_API_TEST_ENV_MISSING".into())
+    );
+}